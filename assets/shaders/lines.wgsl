@@ -1,46 +1,121 @@
-#import bevy_sprite::{mesh2d_vertex_output::VertexOutput}
-const lines_number: u32 = 256;
+#import bevy_sprite::{mesh2d_vertex_output::VertexOutput, mesh2d_view_bindings::globals}
 
 struct LineRendererSettings
 {
-    lines: array<vec4<f32>, lines_number>,
-    width: f32
+    width: f32,
+    screen_space: u32,
+    total_length: f32,
+    pulse_frequency: f32,
+    pulse_amplitude: f32,
+    reveal_fraction: f32,
+    reveal_duration: f32,
+    reveal_start_time: f32,
+    tile_grid: u32
 }
 
 @group(2) @binding(0) var<uniform> properties: LineRendererSettings;
 
+struct Line
+{
+    points: vec4<f32>,
+    color: vec4<f32>,
+    style: vec4<f32>,
+    path_start: f32
+}
+
+const STYLE_DASHED: u32 = 1u;
+const STYLE_DOTTED: u32 = 2u;
+const STYLE_TAPERED: u32 = 3u;
+
+@group(2) @binding(1) var<storage, read> lines: array<Line>;
+// Start and count in tile_lines for every tile, row by row starting at uv (0, 0).
+@group(2) @binding(2) var<storage, read> tiles: array<vec2<u32>>;
+@group(2) @binding(3) var<storage, read> tile_lines: array<u32>;
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    if (is_near_a_line(in.uv)) {
-        return vec4<f32>(1.);
+    let centered_uv = in.uv * 2. - vec2<f32>(1., 1.);
+    let color = line_color_at(centered_uv, line_width(centered_uv), revealed_length());
+    return vec4<f32>(color.rgb * pulse(), color.a);
+}
+
+fn pulse() -> f32 {
+    return 1. + properties.pulse_amplitude * sin(globals.time * properties.pulse_frequency * 6.2831853);
+}
+
+fn revealed_length() -> f32 {
+    var fraction = properties.reveal_fraction;
+    if properties.reveal_duration > 0. {
+        let progress = (globals.time - properties.reveal_start_time) / properties.reveal_duration;
+        fraction = fraction * clamp(progress, 0., 1.);
     }
-    return vec4<f32>(0.);
+    return fraction * properties.total_length;
+}
+
+fn line_width(centered_uv: vec2<f32>) -> f32 {
+    // Size of one screen pixel in uv space, so pixel widths survive zoom and mesh size.
+    let pixel_size = max(length(dpdx(centered_uv)), length(dpdy(centered_uv)));
+    if properties.screen_space != 0u {
+        return properties.width * pixel_size;
+    }
+    return properties.width;
 }
 
-fn is_near_a_line(uv: vec2<f32>) -> bool {
-    let centered_uv = uv * 2. - vec2<f32>(1., 1.);
-    for (var i: u32 = 0u; i < lines_number; i = i + 1u) {
-        let line = properties.lines[i];
-        let dist = distance_to_segment(centered_uv, vec2<f32>(line.x, line.y), vec2<f32>(line.z, line.w));
-        if dist < 0 {
+fn tile_index(centered_uv: vec2<f32>) -> u32 {
+    let grid = f32(properties.tile_grid);
+    let cell = vec2<u32>(clamp(floor((centered_uv + 1.) * 0.5 * grid), vec2<f32>(0.), vec2<f32>(grid - 1.)));
+    return cell.y * properties.tile_grid + cell.x;
+}
+
+fn line_color_at(centered_uv: vec2<f32>, width: f32, revealed_length: f32) -> vec4<f32> {
+    // Only the lines binned in this pixel's tile can reach it.
+    let tile = tiles[tile_index(centered_uv)];
+    for (var i: u32 = tile.x; i < tile.x + tile.y; i = i + 1u) {
+        let line = lines[tile_lines[i]];
+        let hit = distance_to_segment(centered_uv, line.points.xy, line.points.zw);
+        if hit.x < 0 {
+            continue;
+        }
+        let along = hit.y * length(line.points.zw - line.points.xy);
+        if line.path_start + along > revealed_length {
             continue;
         }
-        if dist < properties.width {
-            return true;
+        // Patterns follow the whole path, so dashes carry on across segment boundaries.
+        if is_on_styled_line(line.style, hit.x, line.path_start + along, hit.y, width) {
+            return line.color;
         }
     }
-    return false;
+    return vec4<f32>(0.);
 }
 
-fn distance_to_segment(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+fn is_on_styled_line(style: vec4<f32>, dist: f32, along: f32, t: f32, width: f32) -> bool {
+    switch u32(style.x) {
+        case STYLE_DASHED: {
+            return fract(along / style.y) < 0.5 && dist < width;
+        }
+        case STYLE_DOTTED: {
+            let to_dot = along - round(along / style.y) * style.y;
+            return length(vec2<f32>(to_dot, dist)) < width;
+        }
+        case STYLE_TAPERED: {
+            return dist < width * mix(style.y, style.z, t);
+        }
+        default: {
+            return dist < width;
+        }
+    }
+}
+
+// Returns the distance to the segment and the clamped position along it, or -1 for degenerate segments.
+fn distance_to_segment(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
     let ab = b - a;
     let ap = p - a;
     let ab_length_squared = dot(ab, ab);
     if ab_length_squared == 0. {
-        return -1.;
+        return vec2<f32>(-1., 0.);
     }
     var t = dot(ap, ab) / ab_length_squared;
     t = clamp(t, 0.0, 1.0);
     let closest_point = a + t * ab;
-    return length(p - closest_point);
-}
\ No newline at end of file
+    return vec2<f32>(length(p - closest_point), t);
+}
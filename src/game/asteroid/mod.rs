@@ -1,23 +1,32 @@
 use std::time::Duration;
 
-use avian2d::prelude::{
-    Collider, LinearVelocity, Mass, RigidBody, SpatialQuery, SpatialQueryFilter,
+use avian2d::{
+    math::PI,
+    prelude::{Collider, LinearVelocity, Mass, RigidBody, SpatialQuery, SpatialQueryFilter},
 };
 use bevy::{prelude::*, time::common_conditions::on_timer};
 use ore::{get_lines_for_ore, AsteroidOre, AsteroidOrePlugin, OreType};
+use rand::Rng;
 
 use crate::{
     audio_manager::{AudioManager, PlayAudio2D},
     camera_shake::ShakeCamera,
+    collider::LineColliderShape,
     game::ship::Ship,
     health::{Death, Health},
     level_manager::LevelScoped,
     line_group::LineGroup,
     line_mesh::LineMesh,
-    rand::random_range,
+    rand::{random_range, GameRng, RngStream},
 };
 
 pub mod ore;
+
+const ASTEROID_DENSITY: f32 = 0.0001;
+const MIN_FRACTURE_RADIUS: f32 = 25.;
+const MIN_FRAGMENT_AREA: f32 = 50.;
+const FRAGMENT_SEPARATION_SPEED: f32 = 40.;
+
 pub struct AsteroidPlugin;
 
 impl Plugin for AsteroidPlugin {
@@ -35,11 +44,30 @@ impl Plugin for AsteroidPlugin {
 #[derive(Component)]
 pub struct Asteroid {
     pub radius: f32,
+    pub last_impact: Option<AsteroidImpact>,
 }
 
 impl Asteroid {
     pub fn new(radius: f32) -> Asteroid {
-        Asteroid { radius }
+        Asteroid {
+            radius,
+            last_impact: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct AsteroidImpact {
+    pub position: Vec2,
+    pub direction: Vec2,
+}
+
+impl AsteroidImpact {
+    pub fn new(position: Vec2, direction: Vec2) -> AsteroidImpact {
+        AsteroidImpact {
+            position,
+            direction,
+        }
     }
 }
 
@@ -52,22 +80,26 @@ fn spawn_random_asteroid(
     mut commands: Commands,
     ship_q: Query<&Transform, With<Ship>>,
     spatial_query: SpatialQuery,
+    mut game_rng: ResMut<GameRng>,
 ) {
     let Ok(ship_transform) = ship_q.get_single() else {
         return;
     };
+    let rng = game_rng.stream(RngStream::Spawning);
     let ship_pos = ship_transform.translation.xy();
-    let pos = Circle::default().sample_boundary(&mut rand::thread_rng());
-    let radius = random_range(30.0..80.);
+    let pos = Circle::default().sample_boundary(rng);
+    let radius = random_range(rng, 30.0..80.);
     let world_pos = ship_pos + (pos * 2000.);
     let dir = (ship_pos - world_pos).normalize();
-    let random = random_range(0.0..1.0);
+    let random = random_range(rng, 0.0..1.0);
+    let speed = random_range(rng, 150.0..250.);
     {
         spawn_asteroid(
             &mut commands,
+            &mut game_rng,
             radius,
             world_pos,
-            dir * random_range(150.0..250.),
+            dir * speed,
             if random > 0.8 && radius > 50. {
                 AsteroidContent::Ore(get_lines_for_ore(OreType::Crystal))
             } else {
@@ -80,25 +112,33 @@ fn spawn_random_asteroid(
 
 fn spawn_asteroid(
     commands: &mut Commands,
+    game_rng: &mut GameRng,
     radius: f32,
     world_pos: Vec2,
     velocity: Vec2,
     content: AsteroidContent,
     maybe_spatial_query: Option<&SpatialQuery>,
 ) {
-    let mut asteroid_shape = LineGroup::generate_random_circle(radius, 8, 5.);
-    let shape_as_line = asteroid_shape.to_unique_points_looped();
+    let mut asteroid_shape =
+        LineGroup::generate_random_circle(game_rng.stream(RngStream::Spawning), radius, 8, 5.);
+    let outline = asteroid_shape.clone();
     let mut maybe_ore = None;
     let lines = match content {
         AsteroidContent::Empty => asteroid_shape,
         AsteroidContent::Ore(ore) => {
-            let amount = random_range(6..8);
-            asteroid_shape.extend(ore.scatter_circle(radius / 2., amount, 10., true));
+            let amount = random_range(game_rng.stream(RngStream::Loot), 6..8);
+            asteroid_shape.extend(ore.scatter_circle(
+                game_rng.stream(RngStream::Visuals),
+                radius / 2.,
+                amount,
+                10.,
+                true,
+            ));
             maybe_ore = Some(AsteroidOre::new(OreType::Crystal, amount));
             asteroid_shape
         }
     };
-    let collider = Collider::polyline(shape_as_line, None);
+    let collider = outline.to_collider(LineColliderShape::ConvexDecomposition, 0.);
     if maybe_spatial_query.is_some_and(|spatial_query| {
         !spatial_query
             .shape_intersections(&collider, world_pos, 0., &SpatialQueryFilter::default())
@@ -107,16 +147,12 @@ fn spawn_asteroid(
         return;
     }
     let asteroid = commands
-        .spawn((
-            LineMesh(lines),
-            Transform::from_translation(world_pos.extend(0.)),
-            RigidBody::Dynamic,
-            Mass(1.),
+        .spawn(asteroid_bundle(
+            lines,
+            &outline,
             collider,
-            LinearVelocity(velocity),
-            Health::new_destroy_on_death(10.),
-            Asteroid::new(radius),
-            LevelScoped,
+            Transform::from_translation(world_pos.extend(0.)),
+            velocity,
         ))
         .id();
     if let Some(ore) = maybe_ore {
@@ -124,36 +160,110 @@ fn spawn_asteroid(
     }
 }
 
+fn asteroid_bundle(
+    lines: LineGroup,
+    outline: &LineGroup,
+    collider: Collider,
+    transform: Transform,
+    velocity: Vec2,
+) -> impl Bundle {
+    let area = outline.area();
+    (
+        LineMesh(lines),
+        transform,
+        RigidBody::Dynamic,
+        Mass(area * ASTEROID_DENSITY),
+        collider,
+        LinearVelocity(velocity),
+        Health::new_destroy_on_death(10.),
+        Asteroid::new((area / PI).sqrt()),
+        LevelScoped,
+    )
+}
+
 fn handle_asteroid_destroyed(
     mut commands: Commands,
     mut death_e: EventReader<Death>,
     mut shake_w: EventWriter<ShakeCamera>,
     mut audio_manager: AudioManager,
-    asteroid_q: Query<(&Transform, &LinearVelocity, &Asteroid, Option<&AsteroidOre>)>,
+    asteroid_q: Query<(
+        &Transform,
+        &LinearVelocity,
+        &LineMesh,
+        &Asteroid,
+        Option<&AsteroidOre>,
+    )>,
+    mut game_rng: ResMut<GameRng>,
 ) {
+    let rng = game_rng.stream(RngStream::Spawning);
     for event in death_e.read() {
-        if let Ok((transform, velocity, asteroid, maybe_ore)) = asteroid_q.get(event.entity) {
-            audio_manager.play_sound(PlayAudio2D::new_once("sounds/destroy.wav".to_owned()));
-            shake_w.send(ShakeCamera::new(0.4));
-            if asteroid.radius > 50. && maybe_ore.is_none() {
-                let orthogonal = velocity.normalize().perp() * asteroid.radius / 2.;
-                spawn_asteroid(
-                    &mut commands,
-                    asteroid.radius / 2.,
-                    transform.translation.xy() + orthogonal,
-                    **velocity,
-                    AsteroidContent::Empty,
-                    None,
-                );
-                spawn_asteroid(
-                    &mut commands,
-                    asteroid.radius / 2.,
-                    transform.translation.xy() - orthogonal,
-                    **velocity,
-                    AsteroidContent::Empty,
-                    None,
-                );
+        let Ok((transform, velocity, line_mesh, asteroid, maybe_ore)) =
+            asteroid_q.get(event.entity)
+        else {
+            continue;
+        };
+        audio_manager.play_sound(PlayAudio2D::new_once("sounds/destroy.wav".to_owned()));
+        shake_w.send(ShakeCamera::new(0.4));
+        if maybe_ore.is_some() || asteroid.radius < MIN_FRACTURE_RADIUS {
+            continue;
+        }
+        let inverse_rotation = transform.rotation.inverse();
+        let (local_impact, local_direction) = match asteroid.last_impact {
+            Some(impact) => (
+                (inverse_rotation * (impact.position.extend(0.) - transform.translation)).xy(),
+                (inverse_rotation * impact.direction.extend(0.)).xy(),
+            ),
+            None => (
+                Vec2::ZERO,
+                Vec2::from_angle(random_range(rng, 0.0..2. * PI)),
+            ),
+        };
+        for piece in fracture_outline(rng, &line_mesh.0, local_impact, local_direction) {
+            if piece.area() < MIN_FRAGMENT_AREA {
+                continue;
             }
+            let Some(centroid) = piece.centroid() else {
+                continue;
+            };
+            let piece = piece.offset(-centroid);
+            let world_centroid = transform.transform_point(centroid.extend(0.));
+            let separation = (world_centroid - transform.translation)
+                .xy()
+                .normalize_or_zero()
+                * FRAGMENT_SEPARATION_SPEED;
+            let collider = piece.to_collider(LineColliderShape::ConvexDecomposition, 0.);
+            commands.spawn(asteroid_bundle(
+                piece.clone(),
+                &piece,
+                collider,
+                Transform::from_translation(world_centroid).with_rotation(transform.rotation),
+                **velocity + separation,
+            ));
         }
     }
 }
+
+/// Splits an asteroid outline along a jagged crack going through `impact` in `direction`.
+fn fracture_outline(
+    rng: &mut impl Rng,
+    outline: &LineGroup,
+    impact: Vec2,
+    direction: Vec2,
+) -> Vec<LineGroup> {
+    let reach = outline.calculate_bounding_size() * 2. + impact.length();
+    let jitter = outline.calculate_bounding_size() * 0.15;
+    let direction = direction.normalize_or(Vec2::X);
+    let half_steps: i32 = 3;
+    let crack: Vec<Vec2> = (-half_steps..=half_steps)
+        .map(|step| {
+            let along = reach * step as f32 / half_steps as f32;
+            let offset = if step == 0 || step.abs() == half_steps {
+                0.
+            } else {
+                random_range(rng, -jitter..jitter)
+            };
+            impact + direction * along + direction.perp() * offset
+        })
+        .collect();
+    outline.slice(&crack)
+}
@@ -6,12 +6,16 @@ use bevy::prelude::*;
 use crate::{
     audio_manager::{AudioManager, PlayAudio2D},
     bevy_utils::query_double_mut,
+    collider::LineColliderShape,
     game::ship::{inventory::Inventory, Ship},
     health::Death,
     level_manager::LevelScoped,
+    line::LineStyle,
     line_group::LineGroup,
     line_mesh::LineMesh,
-    rand::{random_range, random_vec2_range},
+    link::{LineLink, LineLinkShape},
+    rand::{random_range, random_vec2_range, GameRng, RngStream},
+    LineWidth,
 };
 
 use super::Asteroid;
@@ -31,6 +35,10 @@ impl AsteroidOre {
 #[derive(Component)]
 pub struct Ore;
 
+/// Ore already linked to the ship pulling it in.
+#[derive(Component)]
+pub struct OreTether;
+
 #[derive(Clone, Copy)]
 pub enum OreType {
     Crystal,
@@ -48,26 +56,28 @@ fn handle_asteroid_destroyed(
     mut commands: Commands,
     mut death_e: EventReader<Death>,
     asteroid_q: Query<(&Transform, &Asteroid, &AsteroidOre)>,
+    mut game_rng: ResMut<GameRng>,
 ) {
+    let rng = game_rng.stream(RngStream::Loot);
     for event in death_e.read() {
         if let Ok((transform, _asteroid, ore)) = asteroid_q.get(event.entity) {
             for _ in 0..ore.amount {
                 let lines = get_lines_for_ore(ore.ore_type);
-                let collider = Collider::polyline(lines.to_unique_points_looped(), None);
+                let collider = lines.to_collider(LineColliderShape::ConvexHull, 0.);
                 let ore = commands
                     .spawn((
                         LineMesh(lines),
                         RigidBody::Dynamic,
-                        ExternalForce::new(random_vec2_range(-3000.0..3000.0))
+                        ExternalForce::new(random_vec2_range(rng, -3000.0..3000.0))
                             .with_persistence(false),
                         LinearDamping(2.),
                         Mass(0.1),
                         collider,
                         Transform::from_translation(
-                            transform.translation + random_vec2_range(-50.0..50.).extend(0.),
+                            transform.translation + random_vec2_range(rng, -50.0..50.).extend(0.),
                         )
                         .with_rotation(Quat::from_rotation_z(
-                            (random_range(0.0..360.0) as f32).to_radians(),
+                            (random_range(rng, 0.0..360.0) as f32).to_radians(),
                         )),
                         Ore,
                         LevelScoped,
@@ -84,14 +94,14 @@ pub fn handle_ore_pickup(
     mut commands: Commands,
     mut audio_manager: AudioManager,
     mut collision_event_reader: EventReader<Collision>,
-    mut ore_q: Query<(Entity, &Transform, &mut ExternalForce, &Ore)>,
-    mut ship_q: Query<(&Transform, &mut Inventory), With<Ship>>,
+    mut ore_q: Query<(Entity, &Transform, &mut ExternalForce, Has<OreTether>, &Ore)>,
+    mut ship_q: Query<(Entity, &Transform, &mut Inventory), With<Ship>>,
     mut parent_q: Query<&Parent>,
 ) {
     let mut already = Vec::new();
 
     for Collision(contacts) in collision_event_reader.read() {
-        let Some(((ship_transform, mut ship_inventory), parent)) = query_double_mut(
+        let Some(((ship, ship_transform, mut ship_inventory), parent)) = query_double_mut(
             &mut ship_q,
             &mut parent_q,
             contacts.entity1,
@@ -99,7 +109,8 @@ pub fn handle_ore_pickup(
         ) else {
             continue;
         };
-        let Ok((ore_entity, ore_transform, mut force, _ore)) = ore_q.get_mut(**parent) else {
+        let Ok((ore_entity, ore_transform, mut force, tethered, _ore)) = ore_q.get_mut(**parent)
+        else {
             continue;
         };
 
@@ -110,6 +121,17 @@ pub fn handle_ore_pickup(
         let diff = ship_transform.translation - ore_transform.translation;
         let dir = diff.normalize().xy();
         force.set_force(dir * 200.);
+        if !tethered {
+            commands.spawn((
+                LineLink::new(ship, ore_entity)
+                    .with_shape(LineLinkShape::Slack { length: 220. })
+                    .with_color(Color::srgb(0.5, 0.9, 1.))
+                    .with_style(LineStyle::Dashed { pattern_length: 8. }),
+                LineWidth::World(0.5),
+                LevelScoped,
+            ));
+            commands.entity(ore_entity).try_insert(OreTether);
+        }
 
         if ore_transform
             .translation
@@ -136,6 +158,8 @@ pub fn get_lines_for_ore(ore: OreType) -> LineGroup {
             Vec2::new(0., 10.),
             Vec2::new(6., 0.),
             Vec2::new(0., -10.),
-        ]),
+        ])
+        .with_color(Color::srgb(0.5, 0.9, 1.))
+        .with_intensity(2.),
     }
 }
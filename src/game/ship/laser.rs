@@ -1,20 +1,20 @@
 use avian2d::{
     math::PI,
-    prelude::{Collider, CollisionStarted, LinearDamping, LinearVelocity, RigidBody, Sensor},
+    prelude::{Collider, CollisionStarted, Sensor},
 };
 use bevy::prelude::*;
 
 use crate::{
     audio_manager::{AudioManager, PlayAudio2D},
-    bevy_utils::query_double,
+    bevy_utils::query_double_mut,
     delayed_despawn::DelayedDespawn,
-    game::asteroid::Asteroid,
+    game::asteroid::{Asteroid, AsteroidImpact},
     health::HealthManager,
     level_manager::LevelScoped,
     line::Line,
     line_group::LineGroup,
     line_mesh::LineMesh,
-    rand::random_range,
+    particles::{ParticlePreset, SpawnBurst},
 };
 
 use super::FireLaser;
@@ -65,39 +65,35 @@ pub fn handle_laser_spawning(
     }
 }
 
+fn laser_sparks() -> ParticlePreset {
+    ParticlePreset::streaks(5.0..11.)
+        .with_count(4..7)
+        .with_speed(300.0..1000.)
+        .with_cone(180., 60.)
+        .with_damping(5.)
+        .with_lifetime(0.05..0.2)
+}
+
 pub fn asteroid_collisions(
     mut commands: Commands,
     mut collision_event_reader: EventReader<CollisionStarted>,
-    laser_q: Query<(Entity, &Transform), With<Laser>>,
-    asteroid_q: Query<(Entity, &Transform), With<Asteroid>>,
+    mut laser_q: Query<(Entity, &Transform), With<Laser>>,
+    mut asteroid_q: Query<(Entity, &mut Asteroid)>,
     mut health_manager: HealthManager,
-    time: Res<Time>,
+    mut burst_e: EventWriter<SpawnBurst>,
 ) {
     for CollisionStarted(e1, e2) in collision_event_reader.read() {
-        let Some(((laser, laser_transfrom), (asteroid, _asteroid_transform))) =
-            query_double(&laser_q, &asteroid_q, *e1, *e2)
+        let Some(((laser, laser_transfrom), (asteroid, mut asteroid_data))) =
+            query_double_mut(&mut laser_q, &mut asteroid_q, *e1, *e2)
         else {
             continue;
         };
+        asteroid_data.last_impact = Some(AsteroidImpact::new(
+            laser_transfrom.translation.xy(),
+            laser_transfrom.up().xy(),
+        ));
         commands.entity(laser).try_despawn();
-        for _ in 0..random_range(4..7) {
-            let pos = laser_transfrom.translation;
-            let rotation = laser_transfrom.rotation
-                * Quat::from_axis_angle(
-                    Vec3::Z,
-                    (180. as f32 + random_range(-60.0..60.)).to_radians(),
-                );
-            let lines = LineGroup::from_line(Line::new(Vec2::new(0., 0.), Vec2::new(0., 10.)))
-                .scaled(random_range(0.5..1.1));
-            commands.spawn((
-                LineMesh(lines),
-                Transform::from_translation(pos).with_rotation(rotation),
-                RigidBody::Dynamic,
-                LinearVelocity((rotation.mul_vec3(Vec3::Y) * random_range(300.0..1000.)).xy()),
-                LinearDamping(5.),
-                DelayedDespawn::new(time.elapsed_secs(), random_range(0.05..0.2)),
-            ));
-        }
+        burst_e.send(SpawnBurst::new(laser_sparks(), *laser_transfrom));
         health_manager.damage(asteroid, 10.);
     }
 }
@@ -1,6 +1,6 @@
 use avian2d::prelude::{
-    AngularVelocity, Collider, CollisionStarted, ExternalForce, LinearVelocity, Mass, RigidBody,
-    Sensor, TransformInterpolation,
+    CollisionStarted, ExternalForce, LinearVelocity, Mass, RigidBody, Sensor,
+    TransformInterpolation,
 };
 use bevy::prelude::*;
 use inventory::{inventory_plugin, Inventory};
@@ -12,12 +12,16 @@ use crate::{
     bevy_utils::query_double,
     blink::Blink,
     camera_shake::ShakeCamera,
+    collider::LineColliderShape,
     game::asteroid::Asteroid,
     health::{DamageTaken, Death, Health, HealthHitInvincibilityTime, HealthManager},
     level_manager::LevelScoped,
     line_group::LineGroup,
     line_mesh::LineMesh,
-    rand::{random_range, random_vec2_range},
+    morph::LineMeshMorph,
+    particles::{ParticlePreset, SpawnBurst},
+    trail::LineTrail,
+    LineWidth,
 };
 
 use super::{CurrentGameState, GameState};
@@ -110,9 +114,10 @@ pub fn spawn_ship(commands: &mut Commands) -> Entity {
     let (ship_shape, thruster_shape) = get_ship_and_thrusters_shape(3);
     let ship = commands
         .spawn((
+            // Slightly smaller than the drawing, so grazing an asteroid doesn't count as a hit.
+            ship_shape.to_collider(LineColliderShape::ConvexHull, -2.),
             LineMesh(ship_shape),
             RigidBody::Dynamic,
-            Collider::circle(10.),
             ExternalForce::default().with_persistence(false),
             Mass(1.),
             Sensor,
@@ -126,6 +131,10 @@ pub fn spawn_ship(commands: &mut Commands) -> Entity {
                 is_thrusting: false,
             },
             Inventory::new(10),
+            LineTrail::new(24, 0.05)
+                .with_max_length(250.)
+                .with_width(1.5, 0.)
+                .with_color(Color::srgba(0.6, 0.8, 1., 0.5)),
             LevelScoped,
         ))
         .id();
@@ -163,42 +172,45 @@ pub fn ship_hurt(
     mut audio_manager: AudioManager,
     mut damage_r: EventReader<DamageTaken>,
     mut shake_w: EventWriter<ShakeCamera>,
-    ship_q: Query<Entity, With<Ship>>,
+    ship_q: Query<(Entity, &LineMesh), With<Ship>>,
 ) {
     for damage in damage_r.read() {
-        if let Ok(ship) = ship_q.get(damage.entity) {
+        if let Ok((ship, line_mesh)) = ship_q.get(damage.entity) {
             audio_manager.play_sound(PlayAudio2D::new_once("sounds/hurt.wav".to_string()));
             shake_w.send(ShakeCamera::new(1.));
-            commands.entity(ship).insert(LineMesh(
-                get_ship_and_thrusters_shape((damage.new_hp / 10.) as u32).0,
-            ));
+            let damaged_shape = get_ship_and_thrusters_shape((damage.new_hp / 10.) as u32).0;
+            commands.entity(ship).insert(
+                LineMeshMorph::new(&line_mesh.0, &damaged_shape, 0.25)
+                    .with_easing(EaseFunction::BackOut),
+            );
         }
     }
 }
 
+fn hull_debris(hull: &LineGroup) -> ParticlePreset {
+    ParticlePreset::shards(hull.clone())
+        .with_speed(0.0..140.)
+        .with_angular_speed(-1.0..1.)
+        .with_inherit_velocity(1.)
+        .with_width(LineWidth::World(0.8))
+}
+
 pub fn ship_death(
     mut commands: Commands,
     mut audio_manager: AudioManager,
     mut death_r: EventReader<Death>,
     ship_q: Query<(Entity, &Transform, &LinearVelocity, &LineMesh), With<Ship>>,
     mut game_state: ResMut<CurrentGameState>,
+    mut burst_e: EventWriter<SpawnBurst>,
 ) {
     for death in death_r.read() {
         audio_manager.play_sound(PlayAudio2D::new_once("sounds/ship_destroy.wav"));
         audio_manager.toggle_audio_off("sounds/thrusters.wav");
         if let Ok((ship, transform, ship_velocity, line_mesh)) = ship_q.get(death.entity) {
-            let lines = &line_mesh.0;
-            for line in lines.get_lines() {
-                commands.spawn((
-                    LineMesh(LineGroup::from_line(*line)),
-                    transform.clone(),
-                    RigidBody::Dynamic,
-                    Mass(1.),
-                    LinearVelocity(ship_velocity.0 + random_vec2_range(-100.0..100.)),
-                    AngularVelocity(random_range(-1.0..1.)),
-                    LevelScoped,
-                ));
-            }
+            burst_e.send(
+                SpawnBurst::new(hull_debris(&line_mesh.0), *transform)
+                    .with_velocity(ship_velocity.0),
+            );
             commands.entity(ship).despawn_recursive();
             game_state.0 = GameState::GAMEOVER;
         }
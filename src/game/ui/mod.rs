@@ -1,7 +1,12 @@
-use bevy::prelude::*;
+use bevy::{math::bounding::Aabb2d, prelude::*};
 
 use crate::{
-    blink::Blink, game::GameState, line::Line, line_group::LineGroup, line_mesh::LineMesh,
+    game::GameState,
+    line::Line,
+    line_group::LineGroup,
+    line_mesh::LineMesh,
+    text::{TextAlign, TextLayout},
+    LineAnimation, LineWidth,
 };
 
 use super::{ship::inventory::InventoryUpdate, CurrentGameState};
@@ -42,22 +47,20 @@ impl StorageLine {
 pub fn spawn_ui(commands: &mut Commands, camera: Entity) {
     let storage_height = 150.;
     let storage_width = 20.;
+    // Where the right aligned labels end, left of the bar.
+    let label_x = -storage_width - 15.;
     let storage_ui_lines = LineGroup::generate_continuous_closed(vec![
         Vec2::new(-storage_width, storage_height),
         Vec2::new(storage_width, storage_height),
         Vec2::new(storage_width, -storage_height),
         Vec2::new(-storage_width, -storage_height),
     ])
-    .concat(
-        LineGroup::text("EMPTY")
-            .scaled(2.)
-            .offset(Vec2::new(-125., -140.)),
-    )
-    .concat(
-        LineGroup::text("FULL")
-            .scaled(2.)
-            .offset(Vec2::new(-105., 140.)),
-    );
+    .concat(storage_label("EMPTY", |bounds| {
+        Vec2::new(label_x, -storage_height - bounds.min.y)
+    }))
+    .concat(storage_label("FULL", |bounds| {
+        Vec2::new(label_x, storage_height - bounds.max.y)
+    }));
 
     let storage_ui = commands
         .spawn((
@@ -82,24 +85,45 @@ pub fn spawn_ui(commands: &mut Commands, camera: Entity) {
 
     commands.entity(camera).add_child(death_ui);
     let game_over = commands
-        .spawn(LineMesh(LineGroup::text("GAME OVER").scaled(7.).centered()))
+        .spawn((
+            LineMesh(
+                TextLayout::new("GAME OVER")
+                    .with_align(TextAlign::Center)
+                    .build()
+                    .scaled(7.),
+            ),
+            LineWidth::World(3.),
+        ))
         .id();
     commands.entity(death_ui).add_child(game_over);
 
     let press_r = commands
         .spawn((
             LineMesh(
-                LineGroup::text("PRESS R TO TRY AGAIN")
+                TextLayout::new("PRESS R TO TRY AGAIN")
+                    .with_align(TextAlign::Center)
+                    .build()
                     .scaled(2.)
-                    .centered()
                     .offset(-Vec2::Y * 100.),
             ),
-            Blink::new(2., true, Visibility::Hidden),
+            LineAnimation::pulse(1., 1.),
         ))
         .id();
     commands.entity(death_ui).add_child(press_r);
 }
 
+/// Right aligned label, placed by `offset` from its scaled bounds.
+fn storage_label(text: &str, offset: impl Fn(Aabb2d) -> Vec2) -> LineGroup {
+    let scale = 2.;
+    let layout = TextLayout::new(text).with_align(TextAlign::Right);
+    let bounds = layout.measure();
+    let bounds = Aabb2d {
+        min: bounds.min * scale,
+        max: bounds.max * scale,
+    };
+    layout.build().scaled(scale).offset(offset(bounds))
+}
+
 fn init_storage(mut line_q: Query<(&mut Transform, &StorageLine), Added<StorageLine>>) {
     for (mut transform, line) in line_q.iter_mut() {
         transform.translation.y = -0.5 * line.max_offset;
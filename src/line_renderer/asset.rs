@@ -0,0 +1,204 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+    utils::hashbrown::HashMap,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{
+    line::LineStyle,
+    line_group::LineGroup,
+    line_mesh::{on_change, LineMesh},
+};
+
+pub struct LineArtPlugin;
+
+impl Plugin for LineArtPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<LineArt>()
+            .register_asset_loader(LineArtLoader)
+            .add_systems(Update, sync_line_art.before(on_change));
+    }
+}
+
+/// Line drawing loaded from a `.lines.ron` file, such as `assets/line_art/diamond.lines.ron`:
+///
+/// ```ron
+/// (
+///     paths: [
+///         (points: [(-6., 0.), (0., 10.), (6., 0.), (0., -10.)], closed: true, color: (0.5, 0.9, 1.), intensity: 2.),
+///         (points: [(0., 10.), (0., -10.)], style: Dashed(pattern_length: 4.)),
+///     ],
+///     anchors: { "top": (0., 10.) },
+/// )
+/// ```
+///
+/// Colors are in sRGB, every path field except `points` is optional.
+#[derive(Asset, TypePath, Clone, Debug, Default)]
+pub struct LineArt {
+    pub lines: LineGroup,
+    /// Named points of the drawing, in the same space as its lines.
+    pub anchors: HashMap<String, Vec2>,
+}
+
+impl LineArt {
+    pub fn anchor(&self, name: &str) -> Option<Vec2> {
+        self.anchors.get(name).copied()
+    }
+}
+
+/// Keeps the entity's `LineMesh` in sync with a `LineArt`, including when the file is edited.
+#[derive(Component, Clone, Debug)]
+pub struct LineArtHandle(pub Handle<LineArt>);
+
+#[derive(Deserialize)]
+struct LineArtFile {
+    #[serde(default)]
+    paths: Vec<LinePath>,
+    #[serde(default)]
+    anchors: HashMap<String, (f32, f32)>,
+}
+
+#[derive(Deserialize)]
+struct LinePath {
+    points: Vec<(f32, f32)>,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default = "default_color")]
+    color: (f32, f32, f32),
+    #[serde(default = "default_intensity")]
+    intensity: f32,
+    #[serde(default)]
+    style: LineStyle,
+}
+
+fn default_color() -> (f32, f32, f32) {
+    (1., 1., 1.)
+}
+
+fn default_intensity() -> f32 {
+    1.
+}
+
+impl From<LineArtFile> for LineArt {
+    fn from(file: LineArtFile) -> LineArt {
+        let mut lines = LineGroup::default();
+        for path in file.paths {
+            let points = path.points.iter().map(|(x, y)| Vec2::new(*x, *y)).collect();
+            let group = if path.closed {
+                LineGroup::generate_continuous_closed(points)
+            } else {
+                LineGroup::generate_continuous(points)
+            };
+            let (red, green, blue) = path.color;
+            lines.extend(
+                group
+                    .with_color(Color::srgb(red, green, blue))
+                    .with_intensity(path.intensity)
+                    .with_style(path.style),
+            );
+        }
+        LineArt {
+            lines,
+            anchors: file
+                .anchors
+                .into_iter()
+                .map(|(name, (x, y))| (name, Vec2::new(x, y)))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct LineArtLoader;
+
+#[derive(Debug, Error)]
+pub enum LineArtLoaderError {
+    #[error("could not read line art: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse line art: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for LineArtLoader {
+    type Asset = LineArt;
+    type Settings = ();
+    type Error = LineArtLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<LineArt, LineArtLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: LineArtFile = ron::de::from_bytes(&bytes)?;
+        Ok(file.into())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lines.ron"]
+    }
+}
+
+fn sync_line_art(
+    mut commands: Commands,
+    mut art_events: EventReader<AssetEvent<LineArt>>,
+    line_arts: Res<Assets<LineArt>>,
+    art_q: Query<(Entity, Ref<LineArtHandle>)>,
+) {
+    let changed: Vec<AssetId<LineArt>> = art_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    for (entity, handle) in art_q.iter() {
+        if !handle.is_changed() && !changed.contains(&handle.0.id()) {
+            continue;
+        }
+        let Some(art) = line_arts.get(&handle.0) else {
+            continue;
+        };
+        commands
+            .entity(entity)
+            .try_insert(LineMesh(art.lines.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Same as the example in the `LineArt` docs.
+    const DIAMOND: &str = include_str!("../../assets/line_art/diamond.lines.ron");
+
+    #[test]
+    fn documented_example_loads() {
+        let file = ron::from_str::<LineArtFile>(DIAMOND).unwrap();
+        let art = LineArt::from(file);
+        let lines = art.lines.get_lines();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[3].b, Vec2::new(-6., 0.));
+        assert_eq!(lines[0].color, Color::srgb(0.5, 0.9, 1.));
+        assert_eq!(lines[0].intensity, 2.);
+        assert_eq!(lines[0].style, LineStyle::default());
+        assert_eq!(lines[4].color, Color::srgb(1., 1., 1.));
+        assert_eq!(lines[4].intensity, 1.);
+        assert_eq!(lines[4].style, LineStyle::Dashed { pattern_length: 4. });
+        assert_eq!(art.anchor("top"), Some(Vec2::new(0., 10.)));
+        assert_eq!(art.anchor("bottom"), None);
+    }
+
+    #[test]
+    fn only_points_are_required() {
+        let file = ron::from_str::<LineArtFile>("(paths: [(points: [(0., 0.), (1., 0.)])])");
+        let art = LineArt::from(file.unwrap());
+        assert_eq!(art.lines.get_lines().len(), 1);
+        assert!(art.anchors.is_empty());
+        assert!(ron::from_str::<LineArtFile>("(paths: [(closed: true)])").is_err());
+    }
+}
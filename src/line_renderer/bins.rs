@@ -0,0 +1,199 @@
+use bevy::prelude::*;
+
+use super::{LineRendererLine, STYLE_TAPERED};
+
+/// Tiles per side of quads drawn with a world width, see `LineRendererSettings::tile_grid`.
+pub const TILE_GRID: u32 = 8;
+
+/// For every tile of a quad split in `grid` by `grid`, the lines that can draw in it.
+/// Indices stay in the order of the lines, so the first line hit is the same with or without bins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineBins {
+    pub grid: u32,
+    /// Start and count in `indices` per tile, row by row starting at uv (0, 0).
+    pub tiles: Vec<UVec2>,
+    pub indices: Vec<u32>,
+}
+
+impl LineBins {
+    /// Bins lines in centered uv space, where anything closer than `width` to a line (scaled by
+    /// its taper) can be drawn by it.
+    pub fn new(lines: &[LineRendererLine], grid: u32, width: f32) -> LineBins {
+        let grid = grid.max(1);
+        if grid == 1 {
+            return LineBins {
+                grid,
+                tiles: vec![UVec2::new(0, lines.len() as u32)],
+                indices: (0..lines.len() as u32).collect(),
+            };
+        }
+        let tile_size = 2. / grid as f32;
+        let mut tiles = Vec::with_capacity((grid * grid) as usize);
+        let mut indices = Vec::new();
+        for y in 0..grid {
+            for x in 0..grid {
+                let min = Vec2::new(x as f32, y as f32) * tile_size - Vec2::ONE;
+                let max = min + Vec2::splat(tile_size);
+                let start = indices.len() as u32;
+                for (i, line) in lines.iter().enumerate() {
+                    let reach = width * line_reach(line);
+                    let (a, b) = (line.points.xy(), line.points.zw());
+                    if segment_touches_box(a, b, min - reach, max + reach) {
+                        indices.push(i as u32);
+                    }
+                }
+                tiles.push(UVec2::new(start, indices.len() as u32 - start));
+            }
+        }
+        LineBins {
+            grid,
+            tiles,
+            indices,
+        }
+    }
+
+    /// Same as `tile_index` in the shader.
+    pub fn tile_at(&self, centered_uv: Vec2) -> usize {
+        let cell = ((centered_uv + Vec2::ONE) * 0.5 * self.grid as f32)
+            .floor()
+            .clamp(Vec2::ZERO, Vec2::splat((self.grid - 1) as f32))
+            .as_uvec2();
+        (cell.y * self.grid + cell.x) as usize
+    }
+
+    pub fn lines_at(&self, centered_uv: Vec2) -> &[u32] {
+        let tile = self.tiles[self.tile_at(centered_uv)];
+        &self.indices[tile.x as usize..(tile.x + tile.y) as usize]
+    }
+}
+
+/// Width multiplier of the widest part of the line.
+fn line_reach(line: &LineRendererLine) -> f32 {
+    if line.style.x as u32 == STYLE_TAPERED {
+        line.style.y.max(line.style.z).max(0.)
+    } else {
+        1.
+    }
+}
+
+/// Clips the segment against the box, slab by slab.
+fn segment_touches_box(a: Vec2, b: Vec2, min: Vec2, max: Vec2) -> bool {
+    let direction = b - a;
+    let (mut enter, mut exit) = (0f32, 1f32);
+    for axis in 0..2 {
+        if direction[axis] == 0. {
+            if a[axis] < min[axis] || a[axis] > max[axis] {
+                return false;
+            }
+            continue;
+        }
+        let t0 = (min[axis] - a[axis]) / direction[axis];
+        let t1 = (max[axis] - a[axis]) / direction[axis];
+        enter = enter.max(t0.min(t1));
+        exit = exit.min(t0.max(t1));
+        if enter > exit {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+
+    use super::*;
+    use crate::line_renderer::line::{Line, LineStyle};
+
+    const WIDTH: f32 = 0.05;
+
+    fn lines(lines: &[Line]) -> Vec<LineRendererLine> {
+        lines.iter().map(|l| LineRendererLine::new(l, 0.)).collect()
+    }
+
+    /// Checks every sampled point the shader could draw for a line finds that line in its tile.
+    fn assert_nothing_missed(lines: &[LineRendererLine], bins: &LineBins) {
+        for (i, line) in lines.iter().enumerate() {
+            let (a, b) = (line.points.xy(), line.points.zw());
+            for step in 0..=100 {
+                let t = step as f32 / 100.;
+                let reach = if line.style.x as u32 == STYLE_TAPERED {
+                    line.style.y.lerp(line.style.z, t)
+                } else {
+                    1.
+                };
+                for angle in 0..16 {
+                    let direction = Vec2::from_angle(angle as f32 / 16. * TAU);
+                    for distance in [0., 0.5, 0.999] {
+                        let p = a.lerp(b, t) + direction * distance * WIDTH * reach;
+                        if p.abs().max_element() > 1. {
+                            continue;
+                        }
+                        assert!(
+                            bins.lines_at(p).contains(&(i as u32)),
+                            "line {i} missing at {p}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solid_lines_crossing_tiles() {
+        let lines = lines(&[
+            Line::new(Vec2::new(-0.9, -0.8), Vec2::new(0.85, 0.7)),
+            // Along tile borders, horizontally and vertically.
+            Line::new(Vec2::new(-1., 0.), Vec2::new(1., 0.)),
+            Line::new(Vec2::new(0.25, -1.), Vec2::new(0.25, 1.)),
+            Line::new(Vec2::new(0.1, 0.1), Vec2::new(0.12, 0.1)),
+        ]);
+        let bins = LineBins::new(&lines, TILE_GRID, WIDTH);
+        assert_nothing_missed(&lines, &bins);
+    }
+
+    #[test]
+    fn tapered_lines() {
+        let lines = lines(&[
+            Line::new(Vec2::new(-0.7, 0.6), Vec2::new(0.6, 0.4)).with_style(LineStyle::Tapered {
+                start: 0.2,
+                end: 4.,
+            }),
+            Line::new(Vec2::new(0.5, -0.9), Vec2::new(-0.4, -0.2))
+                .with_style(LineStyle::Tapered { start: 3., end: 1. }),
+        ]);
+        let bins = LineBins::new(&lines, TILE_GRID, WIDTH);
+        assert_nothing_missed(&lines, &bins);
+    }
+
+    #[test]
+    fn dotted_and_dashed_lines() {
+        let lines = lines(&[
+            Line::new(Vec2::new(-0.95, 0.3), Vec2::new(0.95, -0.35))
+                .with_style(LineStyle::Dotted { spacing: 0.13 }),
+            Line::new(Vec2::new(-0.5, -0.5), Vec2::new(-0.5, 0.9)).with_style(LineStyle::Dashed {
+                pattern_length: 0.2,
+            }),
+        ]);
+        let bins = LineBins::new(&lines, TILE_GRID, WIDTH);
+        assert_nothing_missed(&lines, &bins);
+    }
+
+    #[test]
+    fn far_tiles_skip_the_line() {
+        let lines = lines(&[Line::new(Vec2::new(-0.9, -0.9), Vec2::new(-0.8, -0.8))]);
+        let bins = LineBins::new(&lines, TILE_GRID, WIDTH);
+        assert_eq!(bins.lines_at(Vec2::new(-0.85, -0.85)), &[0]);
+        assert!(bins.lines_at(Vec2::new(0.9, 0.9)).is_empty());
+    }
+
+    #[test]
+    fn single_tile_holds_every_line_in_order() {
+        let lines = lines(&[
+            Line::new(Vec2::new(-0.9, -0.9), Vec2::new(-0.8, -0.8)),
+            Line::new(Vec2::new(0.8, 0.8), Vec2::new(0.9, 0.9)),
+        ]);
+        let bins = LineBins::new(&lines, 1, WIDTH);
+        assert_eq!(bins.lines_at(Vec2::new(0.9, -0.9)), &[0, 1]);
+    }
+}
@@ -0,0 +1,64 @@
+use avian2d::prelude::Collider;
+use bevy::prelude::*;
+
+use super::line_group::LineGroup;
+
+/// Keeps sharp corners from shooting far out when growing a shape.
+const MIN_MITER_SCALE: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LineColliderShape {
+    /// One convex shape around every point, cheap but fills in dents.
+    #[default]
+    ConvexHull,
+    /// Compound of convex parts following the outline, for concave shapes.
+    ConvexDecomposition,
+}
+
+impl LineGroup {
+    /// Solid collider matching the drawn shape. `margin` grows the outline outwards, or shrinks it
+    /// when negative, and only applies to groups forming a single closed loop.
+    /// Groups with no area fall back to a hollow polyline.
+    pub fn to_collider(&self, shape: LineColliderShape, margin: f32) -> Collider {
+        let polygon = self
+            .to_polygon()
+            .map(|polygon| offset_polygon(&polygon, margin));
+        let collider = match (shape, polygon) {
+            (LineColliderShape::ConvexDecomposition, Some(polygon)) => {
+                let count = polygon.len() as u32;
+                let indices = (0..count).map(|i| [i, (i + 1) % count]).collect();
+                Some(Collider::convex_decomposition(polygon, indices))
+            }
+            (_, Some(polygon)) => Collider::convex_hull(polygon),
+            (_, None) => Collider::convex_hull(self.to_unique_points()),
+        };
+        collider.unwrap_or_else(|| Collider::polyline(self.to_unique_points(), None))
+    }
+}
+
+/// Moves every vertex along its corner's bisector so each edge ends up `margin` further out.
+fn offset_polygon(polygon: &[Vec2], margin: f32) -> Vec<Vec2> {
+    if margin == 0. {
+        return polygon.to_vec();
+    }
+    let count = polygon.len();
+    let counter_clockwise =
+        LineGroup::generate_continuous_closed(polygon.to_vec()).signed_area() > 0.;
+    let outward = |from: Vec2, to: Vec2| {
+        let left = (to - from).perp().normalize_or_zero();
+        if counter_clockwise {
+            -left
+        } else {
+            left
+        }
+    };
+    (0..count)
+        .map(|i| {
+            let point = polygon[i];
+            let before = outward(polygon[(i + count - 1) % count], point);
+            let after = outward(point, polygon[(i + 1) % count]);
+            let miter = (before + after).normalize_or_zero();
+            point + miter * margin / miter.dot(before).max(MIN_MITER_SCALE)
+        })
+        .collect()
+}
@@ -0,0 +1,245 @@
+use bevy::{
+    math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume},
+    prelude::*,
+};
+
+use super::line_mesh::{on_change, LineMesh};
+
+pub struct LineCullingPlugin;
+
+impl Plugin for LineCullingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LineCullingSettings>()
+            .add_systems(Update, update_line_culling.before(on_change));
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct LineCullingSettings {
+    /// World units around the camera view where meshes are still kept.
+    pub margin: f32,
+    /// Meshes smaller than this many pixels on screen are drawn simplified.
+    pub lod_pixels: f32,
+}
+
+impl Default for LineCullingSettings {
+    fn default() -> Self {
+        LineCullingSettings {
+            margin: 200.,
+            lod_pixels: 24.,
+        }
+    }
+}
+
+/// What a camera sees, in world space.
+#[derive(Clone, Copy, Debug)]
+pub struct LineView {
+    pub area: Aabb2d,
+    pub pixels_per_unit: f32,
+}
+
+/// Added to every `LineMesh` entity and kept up to date with the cameras.
+/// Culled entities have no mesh at all, the others use a simplified group while `lod` is set.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct LineCulling {
+    pub culled: bool,
+    pub lod: bool,
+    /// Around the lines, in the entity's local space.
+    local_bounds: Option<BoundingCircle>,
+}
+
+impl LineCulling {
+    /// `(culled, lod)` for lines covering `bounds` in world space. Without any view nothing is culled.
+    pub fn evaluate(
+        bounds: BoundingCircle,
+        views: &[LineView],
+        settings: &LineCullingSettings,
+    ) -> (bool, bool) {
+        if views.is_empty() {
+            return (false, false);
+        }
+        let seen_by: Vec<&LineView> = views
+            .iter()
+            .filter(|view| {
+                view.area
+                    .grow(Vec2::splat(settings.margin))
+                    .intersects(&bounds)
+            })
+            .collect();
+        let pixels = seen_by
+            .iter()
+            .map(|view| bounds.radius() * 2. * view.pixels_per_unit)
+            .fold(0., f32::max);
+        let culled = seen_by.is_empty();
+        (culled, !culled && pixels < settings.lod_pixels)
+    }
+
+    /// Simplification tolerance of the lines, about a pixel once they are small enough to use it.
+    pub fn lod_tolerance(&self, settings: &LineCullingSettings) -> f32 {
+        self.local_bounds
+            .map_or(0., |bounds| bounds.radius() * 2. / settings.lod_pixels)
+    }
+}
+
+fn update_line_culling(
+    mut commands: Commands,
+    settings: Res<LineCullingSettings>,
+    camera_q: Query<(&Camera, &GlobalTransform, &OrthographicProjection)>,
+    mut line_q: Query<(
+        Entity,
+        Ref<LineMesh>,
+        &GlobalTransform,
+        Option<&mut LineCulling>,
+    )>,
+) {
+    let views: Vec<LineView> = camera_q
+        .iter()
+        .filter(|(camera, _, _)| camera.is_active)
+        .filter_map(|(camera, transform, projection)| {
+            let viewport = camera.logical_viewport_size()?;
+            let area = projection.area;
+            Some(LineView {
+                area: Aabb2d {
+                    min: area.min + transform.translation().xy(),
+                    max: area.max + transform.translation().xy(),
+                },
+                pixels_per_unit: viewport.x / area.width(),
+            })
+        })
+        .collect();
+
+    for (entity, line, transform, culling) in line_q.iter_mut() {
+        let Some(mut culling) = culling else {
+            commands.entity(entity).try_insert(LineCulling::default());
+            continue;
+        };
+        if line.is_changed() || culling.local_bounds.is_none() {
+            culling.bypass_change_detection().local_bounds = local_bounds(&line);
+        }
+        let Some(local) = culling.local_bounds else {
+            continue;
+        };
+        let scale = transform.compute_transform().scale.xy().abs().max_element();
+        let bounds = BoundingCircle::new(
+            transform.transform_point(local.center.extend(0.)).xy(),
+            local.radius() * scale,
+        );
+        let (culled, lod) = LineCulling::evaluate(bounds, &views, &settings);
+        if culling.culled != culled || culling.lod != lod {
+            culling.culled = culled;
+            culling.lod = lod;
+        }
+    }
+}
+
+fn local_bounds(line: &LineMesh) -> Option<BoundingCircle> {
+    if line.0.get_lines().is_empty() {
+        return None;
+    }
+    let aabb = line.0.calculate_bounding_box();
+    Some(BoundingCircle::new(
+        aabb.center(),
+        aabb.half_size().length(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS: LineCullingSettings = LineCullingSettings {
+        margin: 100.,
+        lod_pixels: 20.,
+    };
+
+    /// 1000 by 1000 units around the origin, one pixel per unit.
+    fn view() -> LineView {
+        LineView {
+            area: Aabb2d::new(Vec2::ZERO, Vec2::splat(500.)),
+            pixels_per_unit: 1.,
+        }
+    }
+
+    #[test]
+    fn inside_is_drawn() {
+        let bounds = BoundingCircle::new(Vec2::new(100., -200.), 50.);
+        assert_eq!(
+            LineCulling::evaluate(bounds, &[view()], &SETTINGS),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn within_margin_is_kept() {
+        let bounds = BoundingCircle::new(Vec2::new(620., 0.), 50.);
+        assert_eq!(
+            LineCulling::evaluate(bounds, &[view()], &SETTINGS),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn outside_is_culled() {
+        let bounds = BoundingCircle::new(Vec2::new(700., 0.), 50.);
+        assert_eq!(
+            LineCulling::evaluate(bounds, &[view()], &SETTINGS),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn any_view_keeps_lines() {
+        let bounds = BoundingCircle::new(Vec2::new(2000., 0.), 50.);
+        let other = LineView {
+            area: Aabb2d::new(Vec2::new(2000., 0.), Vec2::splat(500.)),
+            pixels_per_unit: 1.,
+        };
+        assert_eq!(
+            LineCulling::evaluate(bounds, &[view(), other], &SETTINGS),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn small_on_screen_uses_lod() {
+        // 18 pixels across, under the 20 pixel threshold.
+        let small = BoundingCircle::new(Vec2::ZERO, 9.);
+        assert_eq!(
+            LineCulling::evaluate(small, &[view()], &SETTINGS),
+            (false, true)
+        );
+        // Same size seen by a zoomed in camera covers 36 pixels.
+        let zoomed = LineView {
+            pixels_per_unit: 2.,
+            ..view()
+        };
+        assert_eq!(
+            LineCulling::evaluate(small, &[zoomed], &SETTINGS),
+            (false, false)
+        );
+        let large = BoundingCircle::new(Vec2::ZERO, 11.);
+        assert_eq!(
+            LineCulling::evaluate(large, &[view()], &SETTINGS),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn no_camera_culls_nothing() {
+        let bounds = BoundingCircle::new(Vec2::new(10000., 0.), 1.);
+        assert_eq!(
+            LineCulling::evaluate(bounds, &[], &SETTINGS),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn lod_tolerance_is_about_a_pixel() {
+        let culling = LineCulling {
+            local_bounds: Some(BoundingCircle::new(Vec2::ZERO, 10.)),
+            ..default()
+        };
+        assert_eq!(culling.lod_tolerance(&SETTINGS), 1.);
+        assert_eq!(LineCulling::default().lod_tolerance(&SETTINGS), 0.);
+    }
+}
@@ -0,0 +1,199 @@
+use avian2d::math::PI;
+use bevy::prelude::*;
+
+use super::line_group::LineGroup;
+
+/// Keeps tiny or zero tolerances from flattening a curve into millions of lines.
+const MAX_SEGMENTS: u32 = 1024;
+
+/// Every generator takes a `tolerance`, the largest distance allowed between the real curve and
+/// the lines that approximate it, in the same units as the points.
+impl LineGroup {
+    /// Arc around `center`, angles in degrees counter-clockwise from the x axis.
+    pub fn generate_arc(
+        center: Vec2,
+        radius: f32,
+        start_degrees: f32,
+        sweep_degrees: f32,
+        tolerance: f32,
+    ) -> LineGroup {
+        let sweep = sweep_degrees.to_radians();
+        let segments = arc_segments(radius, sweep.abs(), tolerance);
+        let start = start_degrees.to_radians();
+        let points = (0..=segments)
+            .map(|i| center + Vec2::from_angle(start + sweep * i as f32 / segments as f32) * radius)
+            .collect();
+        LineGroup::generate_continuous(points)
+    }
+
+    pub fn generate_ellipse(half_size: Vec2, tolerance: f32) -> LineGroup {
+        // The flattest part of the ellipse bends like a circle of the larger radius at most.
+        let segments = arc_segments(half_size.max_element(), 2. * PI, tolerance);
+        let points = (0..segments)
+            .map(|i| Vec2::from_angle(2. * PI * i as f32 / segments as f32) * half_size)
+            .collect();
+        LineGroup::generate_continuous_closed(points)
+    }
+
+    pub fn generate_quadratic_bezier(p0: Vec2, p1: Vec2, p2: Vec2, tolerance: f32) -> LineGroup {
+        let segments = bezier_segments(0.25 * (p0 - 2. * p1 + p2).length(), tolerance);
+        let points = (0..=segments)
+            .map(|i| {
+                let t = i as f32 / segments as f32;
+                let u = 1. - t;
+                p0 * u * u + p1 * 2. * u * t + p2 * t * t
+            })
+            .collect();
+        LineGroup::generate_continuous(points)
+    }
+
+    pub fn generate_cubic_bezier(
+        p0: Vec2,
+        p1: Vec2,
+        p2: Vec2,
+        p3: Vec2,
+        tolerance: f32,
+    ) -> LineGroup {
+        LineGroup::generate_continuous(cubic_bezier_points(p0, p1, p2, p3, tolerance))
+    }
+
+    /// Smooth curve going through every point, the ends are left open unless `closed`.
+    pub fn generate_catmull_rom(points: &[Vec2], closed: bool, tolerance: f32) -> LineGroup {
+        if points.len() < 2 {
+            return LineGroup::default();
+        }
+        let count = points.len();
+        let point_at = |i: isize| {
+            if closed {
+                points[i.rem_euclid(count as isize) as usize]
+            } else {
+                points[i.clamp(0, count as isize - 1) as usize]
+            }
+        };
+        let span_count = if closed { count } else { count - 1 };
+        let mut flattened = vec![points[0]];
+        for i in 0..span_count as isize {
+            let (before, start, end, after) = (
+                point_at(i - 1),
+                point_at(i),
+                point_at(i + 1),
+                point_at(i + 2),
+            );
+            let span = cubic_bezier_points(
+                start,
+                start + (end - before) / 6.,
+                end - (after - start) / 6.,
+                end,
+                tolerance,
+            );
+            flattened.extend(span.into_iter().skip(1));
+        }
+        LineGroup::generate_continuous(flattened)
+    }
+}
+
+fn arc_segments(radius: f32, sweep: f32, tolerance: f32) -> u32 {
+    if tolerance <= 0. {
+        return MAX_SEGMENTS;
+    }
+    // At least one line per quarter turn, so a loose tolerance still keeps the arc's outline.
+    let min_segments = ((sweep / (PI / 2.)).ceil() as u32).max(1);
+    if radius <= tolerance {
+        return min_segments;
+    }
+    // A chord spanning `angle` strays at most radius * (1 - cos(angle / 2)) from the arc.
+    let max_angle = 2. * (1. - tolerance / radius).acos();
+    ((sweep / max_angle).ceil() as u32).clamp(min_segments, MAX_SEGMENTS)
+}
+
+/// Wang's formula, `deviation` is the largest second difference of the control points
+/// multiplied by d(d - 1) / 8 for a curve of degree d.
+fn bezier_segments(deviation: f32, tolerance: f32) -> u32 {
+    if tolerance <= 0. {
+        return MAX_SEGMENTS;
+    }
+    ((deviation / tolerance).sqrt().ceil() as u32).clamp(1, MAX_SEGMENTS)
+}
+
+fn cubic_bezier_points(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, tolerance: f32) -> Vec<Vec2> {
+    let deviation = 0.75
+        * (p0 - 2. * p1 + p2)
+            .length()
+            .max((p1 - 2. * p2 + p3).length());
+    let segments = bezier_segments(deviation, tolerance);
+    (0..=segments)
+        .map(|i| {
+            let t = i as f32 / segments as f32;
+            let u = 1. - t;
+            p0 * u * u * u + p1 * 3. * u * u * t + p2 * 3. * u * t * t + p3 * t * t * t
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Largest distance from the real curve, sampled densely, to the lines approximating it.
+    fn max_deviation(group: &LineGroup, curve: impl Fn(f32) -> Vec2) -> f32 {
+        (0..=2000)
+            .map(|i| curve(i as f32 / 2000.))
+            .map(|p| p.distance(group.closest_point(p).unwrap()))
+            .fold(0., f32::max)
+    }
+
+    #[test]
+    fn arc_stays_within_tolerance() {
+        for tolerance in [0.01, 0.1, 1.] {
+            let arc = LineGroup::generate_arc(Vec2::new(3., -2.), 20., 30., 250., tolerance);
+            let deviation = max_deviation(&arc, |t| {
+                Vec2::new(3., -2.) + Vec2::from_angle((30. + 250. * t).to_radians()) * 20.
+            });
+            assert!(deviation <= tolerance + 1e-3, "{deviation} > {tolerance}");
+        }
+    }
+
+    #[test]
+    fn ellipse_stays_within_tolerance() {
+        let half_size = Vec2::new(30., 10.);
+        for tolerance in [0.05, 0.5] {
+            let ellipse = LineGroup::generate_ellipse(half_size, tolerance);
+            let deviation = max_deviation(&ellipse, |t| Vec2::from_angle(2. * PI * t) * half_size);
+            assert!(deviation <= tolerance + 1e-3, "{deviation} > {tolerance}");
+        }
+    }
+
+    #[test]
+    fn beziers_stay_within_tolerance() {
+        let (p0, p1, p2, p3) = (
+            Vec2::new(0., 0.),
+            Vec2::new(10., 40.),
+            Vec2::new(30., -30.),
+            Vec2::new(40., 0.),
+        );
+        for tolerance in [0.01, 0.1, 1.] {
+            let quadratic = LineGroup::generate_quadratic_bezier(p0, p1, p3, tolerance);
+            let deviation = max_deviation(&quadratic, |t| {
+                let u = 1. - t;
+                p0 * u * u + p1 * 2. * u * t + p3 * t * t
+            });
+            assert!(deviation <= tolerance + 1e-3, "{deviation} > {tolerance}");
+            let cubic = LineGroup::generate_cubic_bezier(p0, p1, p2, p3, tolerance);
+            let deviation = max_deviation(&cubic, |t| {
+                let u = 1. - t;
+                p0 * u * u * u + p1 * 3. * u * u * t + p2 * 3. * u * t * t + p3 * t * t * t
+            });
+            assert!(deviation <= tolerance + 1e-3, "{deviation} > {tolerance}");
+        }
+    }
+
+    #[test]
+    fn loose_tolerances_keep_a_line_per_quarter_turn() {
+        let circle = LineGroup::generate_arc(Vec2::ZERO, 1., 0., 360., 5.);
+        assert_eq!(circle.get_lines().len(), 4);
+        let bend = LineGroup::generate_arc(Vec2::ZERO, 1., 0., 100., 5.);
+        assert_eq!(bend.get_lines().len(), 2);
+        let point = LineGroup::generate_arc(Vec2::ZERO, 10., 0., 0., 0.1);
+        assert_eq!(point.get_lines().len(), 1);
+    }
+}
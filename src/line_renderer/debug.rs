@@ -0,0 +1,119 @@
+use bevy::{ecs::system::SystemParam, math::bounding::BoundingVolume, prelude::*};
+
+use super::{
+    line::Line,
+    line_group::LineGroup,
+    line_mesh::{on_change, LineMesh},
+    LineWidth,
+};
+
+/// Above the rest of the scene.
+const DEBUG_LINES_Z: f32 = 10.;
+
+pub struct DebugLinesPlugin;
+
+impl Plugin for DebugLinesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugLineBuffer>()
+            .add_systems(Update, draw_debug_lines.before(on_change));
+    }
+}
+
+struct DebugLineEntry {
+    lines: LineGroup,
+    expires_at: f32,
+    drawn: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct DebugLineBuffer {
+    entries: Vec<DebugLineEntry>,
+    changed: bool,
+}
+
+/// Marks the entity all debug lines are drawn with.
+#[derive(Component)]
+pub struct DebugLinesMesh;
+
+/// Queues world space lines that disappear on their own after `duration` seconds.
+/// A duration of 0 keeps them for a single frame.
+#[derive(SystemParam)]
+pub struct DebugLines<'w> {
+    buffer: ResMut<'w, DebugLineBuffer>,
+    time: Res<'w, Time>,
+}
+
+impl DebugLines<'_> {
+    pub fn line(&mut self, a: Vec2, b: Vec2, duration: f32) {
+        self.push(LineGroup::from_line(Line::new(a, b)), duration);
+    }
+
+    pub fn circle(&mut self, center: Vec2, radius: f32, duration: f32) {
+        self.push(
+            LineGroup::generate_circle(radius, 32).offset(center),
+            duration,
+        );
+    }
+
+    pub fn group(&mut self, lines: &LineGroup, transform: Transform, duration: f32) {
+        let lines = LineGroup::new(
+            lines
+                .get_lines()
+                .iter()
+                .map(|l| l.map_points(|p| transform.transform_point(p.extend(0.)).xy()))
+                .collect(),
+        );
+        self.push(lines, duration);
+    }
+
+    fn push(&mut self, lines: LineGroup, duration: f32) {
+        self.buffer.entries.push(DebugLineEntry {
+            lines,
+            expires_at: self.time.elapsed_secs() + duration,
+            drawn: false,
+        });
+        self.buffer.changed = true;
+    }
+}
+
+fn draw_debug_lines(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut buffer: ResMut<DebugLineBuffer>,
+    debug_q: Query<Entity, With<DebugLinesMesh>>,
+) {
+    let now = time.elapsed_secs();
+    let count = buffer.entries.len();
+    // Every line is drawn at least once, even when queued after this system with no duration.
+    buffer
+        .entries
+        .retain(|entry| !entry.drawn || entry.expires_at > now);
+    if !buffer.changed && buffer.entries.len() == count {
+        return;
+    }
+    buffer.changed = false;
+
+    let mut lines = LineGroup::default();
+    for entry in buffer.entries.iter_mut() {
+        lines.extend(entry.lines.clone());
+        entry.drawn = true;
+    }
+    // The mesh covers the group around its origin, center it to keep the mesh small.
+    let center = if lines.get_lines().is_empty() {
+        Vec2::ZERO
+    } else {
+        lines.calculate_bounding_box().center()
+    };
+    let bundle = (
+        LineMesh(lines.offset(-center)),
+        Transform::from_translation(center.extend(DEBUG_LINES_Z)),
+    );
+    match debug_q.get_single() {
+        Ok(entity) => {
+            commands.entity(entity).insert(bundle);
+        }
+        Err(_) => {
+            commands.spawn((bundle, DebugLinesMesh, LineWidth::Screen(1.)));
+        }
+    }
+}
@@ -0,0 +1,617 @@
+use bevy::prelude::*;
+
+use super::line_group::LineGroup;
+
+/// Advance of the uppercase glyphs, which sit in a box from -4 to 4 on both axes.
+pub const DEFAULT_ADVANCE: f32 = 10.;
+pub const LOWERCASE_ADVANCE: f32 = 8.;
+pub const DIGIT_ADVANCE: f32 = 8.;
+pub const NARROW_ADVANCE: f32 = 4.;
+pub const SPACE_ADVANCE: f32 = 6.;
+pub const ASCENT: f32 = 4.;
+pub const DESCENT: f32 = -7.;
+pub const LINE_HEIGHT: f32 = 14.;
+
+/// Baseline is at y = -4, cap height at y = 4, lowercase x-height at y = 1 and descenders reach y = -7.
+#[derive(Clone, Debug)]
+pub struct Glyph {
+    pub lines: LineGroup,
+    pub advance: f32,
+}
+
+impl Glyph {
+    pub fn new(lines: LineGroup, advance: f32) -> Glyph {
+        Glyph { lines, advance }
+    }
+
+    /// Falls back to an empty glyph of the default advance for characters the font doesn't have.
+    pub fn for_char(c: char) -> Glyph {
+        Glyph::try_for_char(c).unwrap_or_else(|| Glyph::new(LineGroup::default(), DEFAULT_ADVANCE))
+    }
+
+    pub fn try_for_char(c: char) -> Option<Glyph> {
+        match c {
+            'A'..='Z' => Some(uppercase(c)),
+            'a'..='z' => Some(lowercase(c)),
+            '0'..='9' => Some(Glyph::new(digit(c), DIGIT_ADVANCE)),
+            _ => punctuation(c),
+        }
+    }
+}
+
+fn strokes(strokes: &[&[(f32, f32)]]) -> LineGroup {
+    let mut res = LineGroup::default();
+    for stroke in strokes {
+        res.extend(LineGroup::generate_continuous(to_points(stroke)));
+    }
+    res
+}
+
+fn closed(points: &[(f32, f32)]) -> LineGroup {
+    LineGroup::generate_continuous_closed(to_points(points))
+}
+
+fn to_points(points: &[(f32, f32)]) -> Vec<Vec2> {
+    points.iter().map(|(x, y)| Vec2::new(*x, *y)).collect()
+}
+
+fn uppercase(c: char) -> Glyph {
+    let lines = match c {
+        'A' => strokes(&[&[(-4., -4.), (0., 4.), (4., -4.)], &[(-3., 0.), (3., 0.)]]),
+        'B' => strokes(&[&[
+            (-4., 0.),
+            (2., 0.),
+            (3., 1.),
+            (3., 3.),
+            (2., 4.),
+            (-4., 4.),
+            (-4., -4.),
+            (3., -4.),
+            (4., -3.),
+            (4., -1.),
+            (2., 0.),
+        ]]),
+        'C' => strokes(&[&[
+            (4., 4.),
+            (-2., 4.),
+            (-4., 2.),
+            (-4., -2.),
+            (-2., -4.),
+            (4., -4.),
+        ]]),
+        'D' => closed(&[
+            (-4., -4.),
+            (-4., 4.),
+            (1., 4.),
+            (4., 1.),
+            (4., -1.),
+            (1., -4.),
+        ]),
+        'E' => strokes(&[
+            &[(3., -4.), (-4., -4.), (-4., 4.), (3., 4.)],
+            &[(-4., 0.), (2., 0.)],
+        ]),
+        'F' => strokes(&[&[(-4., -4.), (-4., 4.), (3., 4.)], &[(-4., 0.), (2., 0.)]]),
+        'G' => strokes(&[&[
+            (4., 4.),
+            (-4., 3.),
+            (-4., -3.),
+            (4., -4.),
+            (4., 0.),
+            (0., 0.),
+        ]]),
+        'H' => strokes(&[
+            &[(-4., 4.), (-4., -4.)],
+            &[(4., 4.), (4., -4.)],
+            &[(-4., 0.), (4., 0.)],
+        ]),
+        'I' => strokes(&[&[(0., -4.), (0., 4.)]]),
+        'J' => strokes(&[&[(4., 4.), (4., -2.), (2., -4.), (-2., -4.), (-4., -2.)]]),
+        'K' => strokes(&[&[(-4., 4.), (-4., -4.)], &[(4., 4.), (-4., 0.), (4., -4.)]]),
+        'L' => strokes(&[&[(-4., 4.), (-4., -4.), (2., -4.)]]),
+        'M' => strokes(&[&[(-4., -4.), (-2., 4.), (0., 0.), (2., 4.), (4., -4.)]]),
+        'N' => strokes(&[&[(-4., -4.), (-4., 4.), (4., -4.), (4., 4.)]]),
+        'O' => closed(&[
+            (-2., -4.),
+            (2., -4.),
+            (4., 0.),
+            (2., 4.),
+            (-2., 4.),
+            (-4., 0.),
+        ]),
+        'P' => strokes(&[&[
+            (-3., -4.),
+            (-3., 4.),
+            (2., 4.),
+            (3., 2.),
+            (2., 0.),
+            (-3., 0.),
+        ]]),
+        'Q' => closed(&[
+            (-2., -4.),
+            (2., -4.),
+            (4., 0.),
+            (2., 4.),
+            (-2., 4.),
+            (-4., 0.),
+        ])
+        .concat(strokes(&[&[(1., -2.), (4., -5.)]])),
+        'R' => strokes(&[&[
+            (-3., -4.),
+            (-3., 4.),
+            (2., 4.),
+            (3., 2.),
+            (2., 0.),
+            (-3., 0.),
+            (3., -4.),
+        ]]),
+        'S' => strokes(&[&[
+            (3., 4.),
+            (-4., 4.),
+            (-4., 0.),
+            (3., 0.),
+            (3., -4.),
+            (-4., -4.),
+        ]]),
+        'T' => strokes(&[&[(0., -4.), (0., 4.)], &[(-4., 4.), (4., 4.)]]),
+        'U' => strokes(&[&[
+            (-4., 4.),
+            (-4., -2.),
+            (-2., -4.),
+            (2., -4.),
+            (4., -2.),
+            (4., 4.),
+        ]]),
+        'V' => strokes(&[&[(-3., 4.), (0., -4.), (3., 4.)]]),
+        'W' => strokes(&[&[(-4., 4.), (-2., -4.), (0., 0.), (2., -4.), (4., 4.)]]),
+        'X' => strokes(&[&[(-4., 4.), (4., -4.)], &[(-4., -4.), (4., 4.)]]),
+        'Y' => strokes(&[&[(-3., 4.), (0., 0.), (3., 4.)], &[(0., 0.), (0., -4.)]]),
+        'Z' => strokes(&[&[(-4., 4.), (4., 4.), (-4., -4.), (4., -4.)]]),
+        _ => LineGroup::default(),
+    };
+    let advance = match c {
+        'I' => SPACE_ADVANCE,
+        _ => DEFAULT_ADVANCE,
+    };
+    Glyph::new(lines, advance)
+}
+
+fn lowercase(c: char) -> Glyph {
+    let lines = match c {
+        'a' => strokes(&[
+            &[(-2., 1.), (2., 1.), (3., 0.), (3., -4.)],
+            &[
+                (3., -1.),
+                (-2., -1.),
+                (-3., -2.),
+                (-3., -3.),
+                (-2., -4.),
+                (3., -4.),
+            ],
+        ]),
+        'b' => strokes(&[&[
+            (-3., 4.),
+            (-3., -4.),
+            (2., -4.),
+            (3., -3.),
+            (3., 0.),
+            (2., 1.),
+            (-3., 1.),
+        ]]),
+        'c' => strokes(&[&[
+            (3., 1.),
+            (-2., 1.),
+            (-3., 0.),
+            (-3., -3.),
+            (-2., -4.),
+            (3., -4.),
+        ]]),
+        'd' => strokes(&[&[
+            (3., 4.),
+            (3., -4.),
+            (-2., -4.),
+            (-3., -3.),
+            (-3., 0.),
+            (-2., 1.),
+            (3., 1.),
+        ]]),
+        'e' => strokes(&[&[
+            (-3., -1.5),
+            (3., -1.5),
+            (3., 0.),
+            (2., 1.),
+            (-2., 1.),
+            (-3., 0.),
+            (-3., -3.),
+            (-2., -4.),
+            (3., -4.),
+        ]]),
+        'f' => strokes(&[
+            &[(2., 4.), (0., 4.), (-1., 3.), (-1., -4.)],
+            &[(-3., 1.), (2., 1.)],
+        ]),
+        'g' => strokes(&[&[
+            (3., -4.),
+            (-2., -4.),
+            (-3., -3.),
+            (-3., 0.),
+            (-2., 1.),
+            (3., 1.),
+            (3., -6.),
+            (2., -7.),
+            (-3., -7.),
+        ]]),
+        'h' => strokes(&[
+            &[(-3., 4.), (-3., -4.)],
+            &[(-3., 1.), (2., 1.), (3., 0.), (3., -4.)],
+        ]),
+        'i' => strokes(&[&[(0., 1.), (0., -4.)], &[(0., 2.5), (0., 3.)]]),
+        'j' => strokes(&[
+            &[(1., 1.), (1., -6.), (0., -7.), (-2., -7.)],
+            &[(1., 2.5), (1., 3.)],
+        ]),
+        'k' => strokes(&[&[(-3., 4.), (-3., -4.)], &[(3., 1.), (-3., -2.), (3., -4.)]]),
+        'l' => strokes(&[&[(0., 4.), (0., -3.), (1., -4.)]]),
+        'm' => strokes(&[
+            &[(-3., -4.), (-3., 1.), (-1., 1.), (0., 0.), (0., -4.)],
+            &[(0., 0.), (1., 1.), (2., 1.), (3., 0.), (3., -4.)],
+        ]),
+        'n' => strokes(&[
+            &[(-3., -4.), (-3., 1.)],
+            &[(-3., 0.), (-2., 1.), (2., 1.), (3., 0.), (3., -4.)],
+        ]),
+        'o' => closed(&[
+            (-2., 1.),
+            (2., 1.),
+            (3., 0.),
+            (3., -3.),
+            (2., -4.),
+            (-2., -4.),
+            (-3., -3.),
+            (-3., 0.),
+        ]),
+        'p' => strokes(&[&[
+            (-3., -7.),
+            (-3., 1.),
+            (2., 1.),
+            (3., 0.),
+            (3., -3.),
+            (2., -4.),
+            (-3., -4.),
+        ]]),
+        'q' => strokes(&[&[
+            (3., -7.),
+            (3., 1.),
+            (-2., 1.),
+            (-3., 0.),
+            (-3., -3.),
+            (-2., -4.),
+            (3., -4.),
+        ]]),
+        'r' => strokes(&[&[(-3., -4.), (-3., 1.)], &[(-3., -1.), (-1., 1.), (3., 1.)]]),
+        's' => strokes(&[&[
+            (3., 1.),
+            (-2., 1.),
+            (-3., 0.),
+            (-3., -1.),
+            (-2., -1.5),
+            (2., -1.5),
+            (3., -2.),
+            (3., -3.),
+            (2., -4.),
+            (-3., -4.),
+        ]]),
+        't' => strokes(&[
+            &[(-1., 4.), (-1., -3.), (0., -4.), (2., -4.)],
+            &[(-3., 1.), (2., 1.)],
+        ]),
+        'u' => strokes(&[
+            &[(-3., 1.), (-3., -3.), (-2., -4.), (3., -4.)],
+            &[(3., 1.), (3., -4.)],
+        ]),
+        'v' => strokes(&[&[(-3., 1.), (0., -4.), (3., 1.)]]),
+        'w' => strokes(&[&[(-3., 1.), (-2., -4.), (0., -1.), (2., -4.), (3., 1.)]]),
+        'x' => strokes(&[&[(-3., 1.), (3., -4.)], &[(-3., -4.), (3., 1.)]]),
+        'y' => strokes(&[&[(-3., 1.), (0., -3.)], &[(3., 1.), (-1., -7.), (-3., -7.)]]),
+        'z' => strokes(&[&[(-3., 1.), (3., 1.), (-3., -4.), (3., -4.)]]),
+        _ => LineGroup::default(),
+    };
+    let advance = match c {
+        'i' | 'l' => NARROW_ADVANCE,
+        'j' => SPACE_ADVANCE,
+        _ => LOWERCASE_ADVANCE,
+    };
+    Glyph::new(lines, advance)
+}
+
+fn digit(c: char) -> LineGroup {
+    match c {
+        '0' => closed(&[
+            (-1., -4.),
+            (1., -4.),
+            (3., -2.),
+            (3., 2.),
+            (1., 4.),
+            (-1., 4.),
+            (-3., 2.),
+            (-3., -2.),
+        ])
+        .concat(strokes(&[&[(-2., -3.), (2., 3.)]])),
+        '1' => strokes(&[&[(-2., 2.), (0., 4.), (0., -4.)], &[(-2., -4.), (2., -4.)]]),
+        '2' => strokes(&[&[
+            (-3., 2.),
+            (-1., 4.),
+            (1., 4.),
+            (3., 2.),
+            (3., 1.),
+            (-3., -4.),
+            (3., -4.),
+        ]]),
+        '3' => strokes(&[&[
+            (-3., 4.),
+            (3., 4.),
+            (0., 0.),
+            (2., 0.),
+            (3., -1.),
+            (3., -3.),
+            (2., -4.),
+            (-3., -4.),
+        ]]),
+        '4' => strokes(&[&[(1., -4.), (1., 4.), (-3., -1.), (3., -1.)]]),
+        '5' => strokes(&[&[
+            (3., 4.),
+            (-3., 4.),
+            (-3., 0.),
+            (2., 0.),
+            (3., -1.),
+            (3., -3.),
+            (2., -4.),
+            (-3., -4.),
+        ]]),
+        '6' => strokes(&[&[
+            (2., 4.),
+            (-1., 4.),
+            (-3., 2.),
+            (-3., -3.),
+            (-2., -4.),
+            (2., -4.),
+            (3., -3.),
+            (3., -1.),
+            (2., 0.),
+            (-3., 0.),
+        ]]),
+        '7' => strokes(&[&[(-3., 4.), (3., 4.), (-1., -4.)]]),
+        '8' => closed(&[
+            (-2., 0.),
+            (-3., 1.),
+            (-3., 3.),
+            (-2., 4.),
+            (2., 4.),
+            (3., 3.),
+            (3., 1.),
+            (2., 0.),
+        ])
+        .concat(strokes(&[&[
+            (-2., 0.),
+            (-3., -1.),
+            (-3., -3.),
+            (-2., -4.),
+            (2., -4.),
+            (3., -3.),
+            (3., -1.),
+            (2., 0.),
+        ]])),
+        '9' => strokes(&[&[
+            (-2., -4.),
+            (1., -4.),
+            (3., -2.),
+            (3., 3.),
+            (2., 4.),
+            (-2., 4.),
+            (-3., 3.),
+            (-3., 1.),
+            (-2., 0.),
+            (3., 0.),
+        ]]),
+        _ => LineGroup::default(),
+    }
+}
+
+fn punctuation(c: char) -> Option<Glyph> {
+    let glyph = match c {
+        ' ' => Glyph::new(LineGroup::default(), SPACE_ADVANCE),
+        '.' => Glyph::new(strokes(&[&[(0., -4.), (0., -3.)]]), NARROW_ADVANCE),
+        ',' => Glyph::new(strokes(&[&[(0., -3.), (-1., -5.)]]), NARROW_ADVANCE),
+        ':' => Glyph::new(
+            strokes(&[&[(0., -4.), (0., -3.)], &[(0., 1.), (0., 2.)]]),
+            NARROW_ADVANCE,
+        ),
+        ';' => Glyph::new(
+            strokes(&[&[(0., -3.), (-1., -5.)], &[(0., 1.), (0., 2.)]]),
+            NARROW_ADVANCE,
+        ),
+        '!' => Glyph::new(
+            strokes(&[&[(0., 4.), (0., -1.)], &[(0., -3.), (0., -4.)]]),
+            NARROW_ADVANCE,
+        ),
+        '?' => Glyph::new(
+            strokes(&[
+                &[
+                    (-3., 3.),
+                    (-2., 4.),
+                    (2., 4.),
+                    (3., 3.),
+                    (3., 1.),
+                    (0., -1.),
+                    (0., -2.),
+                ],
+                &[(0., -3.), (0., -4.)],
+            ]),
+            LOWERCASE_ADVANCE,
+        ),
+        '\'' => Glyph::new(strokes(&[&[(0., 4.), (0., 2.)]]), NARROW_ADVANCE),
+        '"' => Glyph::new(
+            strokes(&[&[(-1., 4.), (-1., 2.)], &[(1., 4.), (1., 2.)]]),
+            SPACE_ADVANCE,
+        ),
+        '|' => Glyph::new(strokes(&[&[(0., 4.), (0., -4.)]]), NARROW_ADVANCE),
+        '(' => Glyph::new(
+            strokes(&[&[(1., 4.), (-1., 2.), (-1., -2.), (1., -4.)]]),
+            SPACE_ADVANCE,
+        ),
+        ')' => Glyph::new(
+            strokes(&[&[(-1., 4.), (1., 2.), (1., -2.), (-1., -4.)]]),
+            SPACE_ADVANCE,
+        ),
+        '[' => Glyph::new(
+            strokes(&[&[(1., 4.), (-1., 4.), (-1., -4.), (1., -4.)]]),
+            SPACE_ADVANCE,
+        ),
+        ']' => Glyph::new(
+            strokes(&[&[(-1., 4.), (1., 4.), (1., -4.), (-1., -4.)]]),
+            SPACE_ADVANCE,
+        ),
+        '-' => Glyph::new(strokes(&[&[(-3., 0.), (3., 0.)]]), LOWERCASE_ADVANCE),
+        '+' => Glyph::new(
+            strokes(&[&[(-3., 0.), (3., 0.)], &[(0., -3.), (0., 3.)]]),
+            LOWERCASE_ADVANCE,
+        ),
+        '=' => Glyph::new(
+            strokes(&[&[(-3., 1.5), (3., 1.5)], &[(-3., -1.5), (3., -1.5)]]),
+            LOWERCASE_ADVANCE,
+        ),
+        '*' => Glyph::new(
+            strokes(&[
+                &[(0., 3.), (0., -1.)],
+                &[(-2., 2.), (2., 0.)],
+                &[(-2., 0.), (2., 2.)],
+            ]),
+            LOWERCASE_ADVANCE,
+        ),
+        '/' => Glyph::new(strokes(&[&[(-3., -4.), (3., 4.)]]), LOWERCASE_ADVANCE),
+        '\\' => Glyph::new(strokes(&[&[(-3., 4.), (3., -4.)]]), LOWERCASE_ADVANCE),
+        '<' => Glyph::new(
+            strokes(&[&[(3., 3.), (-3., 0.), (3., -3.)]]),
+            LOWERCASE_ADVANCE,
+        ),
+        '>' => Glyph::new(
+            strokes(&[&[(-3., 3.), (3., 0.), (-3., -3.)]]),
+            LOWERCASE_ADVANCE,
+        ),
+        '^' => Glyph::new(strokes(&[&[(-2., 2.), (0., 4.), (2., 2.)]]), SPACE_ADVANCE),
+        '_' => Glyph::new(strokes(&[&[(-4., -4.), (4., -4.)]]), DEFAULT_ADVANCE),
+        '#' => Glyph::new(
+            strokes(&[
+                &[(-1., 4.), (-2., -4.)],
+                &[(2., 4.), (1., -4.)],
+                &[(-3., 1.5), (3., 1.5)],
+                &[(-3., -1.5), (3., -1.5)],
+            ]),
+            LOWERCASE_ADVANCE,
+        ),
+        '%' => Glyph::new(
+            strokes(&[&[(-3., -4.), (3., 4.)]])
+                .concat(closed(&[(-3., 4.), (-1., 4.), (-1., 2.), (-3., 2.)]))
+                .concat(closed(&[(1., -2.), (3., -2.), (3., -4.), (1., -4.)])),
+            LOWERCASE_ADVANCE,
+        ),
+        '$' => Glyph::new(
+            strokes(&[
+                &[
+                    (3., 3.),
+                    (-3., 3.),
+                    (-3., 0.),
+                    (3., 0.),
+                    (3., -3.),
+                    (-3., -3.),
+                ],
+                &[(0., 4.), (0., -4.)],
+            ]),
+            LOWERCASE_ADVANCE,
+        ),
+        '@' => Glyph::new(
+            strokes(&[&[
+                (2., -1.),
+                (2., 2.),
+                (-1., 2.),
+                (-1., -1.),
+                (4., -1.),
+                (4., 4.),
+                (-4., 4.),
+                (-4., -4.),
+                (4., -4.),
+            ]]),
+            DEFAULT_ADVANCE,
+        ),
+        '&' => Glyph::new(
+            strokes(&[&[
+                (4., -4.),
+                (-2., 2.),
+                (-2., 3.),
+                (-1., 4.),
+                (1., 4.),
+                (2., 3.),
+                (2., 2.),
+                (-4., -2.),
+                (-4., -3.),
+                (-3., -4.),
+                (1., -4.),
+                (4., -1.),
+            ]]),
+            DEFAULT_ADVANCE,
+        ),
+        '{' => Glyph::new(
+            strokes(&[&[
+                (1.5, 4.),
+                (0.5, 4.),
+                (0.5, 1.),
+                (-1.5, 0.),
+                (0.5, -1.),
+                (0.5, -4.),
+                (1.5, -4.),
+            ]]),
+            SPACE_ADVANCE,
+        ),
+        '}' => Glyph::new(
+            strokes(&[&[
+                (-1.5, 4.),
+                (-0.5, 4.),
+                (-0.5, 1.),
+                (1.5, 0.),
+                (-0.5, -1.),
+                (-0.5, -4.),
+                (-1.5, -4.),
+            ]]),
+            SPACE_ADVANCE,
+        ),
+        '~' => Glyph::new(
+            strokes(&[&[(-3., -0.5), (-1.5, 0.5), (1.5, -0.5), (3., 0.5)]]),
+            LOWERCASE_ADVANCE,
+        ),
+        '`' => Glyph::new(strokes(&[&[(-1., 4.), (1., 2.)]]), NARROW_ADVANCE),
+        _ => return None,
+    };
+    Some(glyph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_printable_ascii_character_has_a_glyph() {
+        for c in ' '..='~' {
+            let glyph = Glyph::try_for_char(c).unwrap_or_else(|| panic!("no glyph for {c:?}"));
+            assert!(glyph.advance > 0., "{c:?}");
+            assert_eq!(glyph.lines.get_lines().is_empty(), c == ' ', "{c:?}");
+        }
+    }
+
+    #[test]
+    fn glyphs_stay_within_the_font_metrics() {
+        for c in '!'..='~' {
+            let bounds = Glyph::for_char(c).lines.calculate_bounding_box();
+            assert!(bounds.max.y <= ASCENT && bounds.min.y >= DESCENT, "{c:?}");
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use bevy::prelude::*;
+
+use super::{line::Line, line_group::LineGroup};
+
+const EPSILON: f32 = 1e-6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineRayHit {
+    pub distance: f32,
+    pub point: Vec2,
+    /// Unit normal of the hit line, facing the ray origin.
+    pub normal: Vec2,
+    pub line_index: usize,
+}
+
+impl Line {
+    pub fn length(&self) -> f32 {
+        self.a.distance(self.b)
+    }
+
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        let ab = self.b - self.a;
+        let length_squared = ab.length_squared();
+        if length_squared < EPSILON {
+            return self.a;
+        }
+        let t = ((point - self.a).dot(ab) / length_squared).clamp(0., 1.);
+        self.a + ab * t
+    }
+
+    /// Returns the position of the intersection along both lines, as fractions from `a` to `b`.
+    pub fn intersection_params(&self, other: &Line) -> Option<(f32, f32)> {
+        let r = self.b - self.a;
+        let s = other.b - other.a;
+        let denominator = r.perp_dot(s);
+        if denominator.abs() < EPSILON {
+            return None;
+        }
+        let diff = other.a - self.a;
+        let t = diff.perp_dot(s) / denominator;
+        let u = diff.perp_dot(r) / denominator;
+        if !(0. ..=1.).contains(&t) || !(0. ..=1.).contains(&u) {
+            return None;
+        }
+        Some((t, u))
+    }
+
+    pub fn intersection(&self, other: &Line) -> Option<Vec2> {
+        let (t, _) = self.intersection_params(other)?;
+        Some(self.a.lerp(self.b, t))
+    }
+
+    /// Distance along the ray to this line, if the ray hits it.
+    pub fn ray_distance(&self, origin: Vec2, direction: Dir2) -> Option<f32> {
+        let s = self.b - self.a;
+        let denominator = direction.perp_dot(s);
+        if denominator.abs() < EPSILON {
+            return None;
+        }
+        let diff = self.a - origin;
+        let distance = diff.perp_dot(s) / denominator;
+        let u = diff.perp_dot(*direction) / denominator;
+        if distance < 0. || !(0. ..=1.).contains(&u) {
+            return None;
+        }
+        Some(distance)
+    }
+}
+
+impl LineGroup {
+    pub fn intersections(&self, line: &Line) -> Vec<Vec2> {
+        self.get_lines()
+            .iter()
+            .filter_map(|l| l.intersection(line))
+            .collect()
+    }
+
+    pub fn cast_ray(&self, origin: Vec2, direction: Dir2, max_distance: f32) -> Option<LineRayHit> {
+        let mut closest: Option<LineRayHit> = None;
+        for (line_index, line) in self.get_lines().iter().enumerate() {
+            let Some(distance) = line.ray_distance(origin, direction) else {
+                continue;
+            };
+            if distance > max_distance || closest.is_some_and(|hit| hit.distance <= distance) {
+                continue;
+            }
+            let mut normal = (line.b - line.a).perp().normalize_or_zero();
+            if normal.dot(*direction) > 0. {
+                normal = -normal;
+            }
+            closest = Some(LineRayHit {
+                distance,
+                point: origin + direction * distance,
+                normal,
+                line_index,
+            });
+        }
+        closest
+    }
+
+    pub fn closest_point(&self, point: Vec2) -> Option<Vec2> {
+        self.get_lines()
+            .iter()
+            .map(|l| l.closest_point(point))
+            .min_by(|a, b| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })
+    }
+
+    /// Even-odd test, only meaningful when the group forms closed loops.
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let mut inside = false;
+        for line in self.get_lines() {
+            let (a, b) = (line.a, line.b);
+            if (a.y > point.y) != (b.y > point.y) {
+                let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// Positive for counter-clockwise loops. Assumes every line of the group goes the same way round.
+    pub fn signed_area(&self) -> f32 {
+        self.get_lines()
+            .iter()
+            .map(|l| l.a.perp_dot(l.b))
+            .sum::<f32>()
+            / 2.
+    }
+
+    pub fn area(&self) -> f32 {
+        self.signed_area().abs()
+    }
+
+    pub fn perimeter(&self) -> f32 {
+        self.get_lines().iter().map(|l| l.length()).sum()
+    }
+
+    /// Area centroid when the group is a single closed loop, the length-weighted average of its
+    /// lines otherwise.
+    pub fn centroid(&self) -> Option<Vec2> {
+        if self.get_lines().is_empty() {
+            return None;
+        }
+        if let Some(polygon) = self.to_polygon() {
+            let edges = || polygon.iter().zip(polygon.iter().cycle().skip(1));
+            let signed_area = edges().map(|(a, b)| a.perp_dot(*b)).sum::<f32>() / 2.;
+            if signed_area.abs() > EPSILON {
+                let sum: Vec2 = edges().map(|(a, b)| (*a + *b) * a.perp_dot(*b)).sum();
+                return Some(sum / (6. * signed_area));
+            }
+        }
+        let perimeter = self.perimeter();
+        if perimeter < EPSILON {
+            return Some(self.get_lines()[0].a);
+        }
+        let sum: Vec2 = self
+            .get_lines()
+            .iter()
+            .map(|l| (l.a + l.b) / 2. * l.length())
+            .sum();
+        Some(sum / perimeter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counter-clockwise square from (0, 0) to (10, 10).
+    fn square() -> LineGroup {
+        LineGroup::generate_continuous_closed(vec![
+            Vec2::new(0., 0.),
+            Vec2::new(10., 0.),
+            Vec2::new(10., 10.),
+            Vec2::new(0., 10.),
+        ])
+    }
+
+    #[test]
+    fn crossing_lines_intersect() {
+        let a = Line::new(Vec2::new(0., 0.), Vec2::new(10., 10.));
+        let b = Line::new(Vec2::new(0., 10.), Vec2::new(10., 0.));
+        assert_eq!(a.intersection(&b), Some(Vec2::new(5., 5.)));
+        assert_eq!(a.intersection_params(&b), Some((0.5, 0.5)));
+    }
+
+    #[test]
+    fn parallel_or_distant_lines_dont_intersect() {
+        let a = Line::new(Vec2::new(0., 0.), Vec2::new(10., 0.));
+        let parallel = Line::new(Vec2::new(0., 1.), Vec2::new(10., 1.));
+        let short = Line::new(Vec2::new(5., 1.), Vec2::new(5., 3.));
+        assert_eq!(a.intersection(&parallel), None);
+        assert_eq!(a.intersection(&short), None);
+    }
+
+    #[test]
+    fn group_intersections() {
+        let cut = Line::new(Vec2::new(-5., 5.), Vec2::new(15., 5.));
+        let mut hits = square().intersections(&cut);
+        hits.sort_by(|a, b| a.x.total_cmp(&b.x));
+        assert_eq!(hits, vec![Vec2::new(0., 5.), Vec2::new(10., 5.)]);
+    }
+
+    #[test]
+    fn ray_hits_closest_line() {
+        let hit = square().cast_ray(Vec2::new(5., 5.), Dir2::X, 100.).unwrap();
+        assert_eq!(hit.distance, 5.);
+        assert_eq!(hit.point, Vec2::new(10., 5.));
+        assert_eq!(hit.normal, Vec2::NEG_X);
+        assert_eq!(hit.line_index, 1);
+
+        let from_outside = square()
+            .cast_ray(Vec2::new(-5., 5.), Dir2::X, 100.)
+            .unwrap();
+        assert_eq!(from_outside.distance, 5.);
+        assert_eq!(from_outside.normal, Vec2::NEG_X);
+    }
+
+    #[test]
+    fn ray_misses() {
+        assert_eq!(square().cast_ray(Vec2::new(5., 5.), Dir2::X, 4.), None);
+        assert_eq!(square().cast_ray(Vec2::new(20., 5.), Dir2::X, 100.), None);
+    }
+
+    #[test]
+    fn closest_point() {
+        let square = square();
+        assert_eq!(
+            square.closest_point(Vec2::new(5., -3.)),
+            Some(Vec2::new(5., 0.))
+        );
+        assert_eq!(
+            square.closest_point(Vec2::new(12., 14.)),
+            Some(Vec2::new(10., 10.))
+        );
+        assert_eq!(LineGroup::default().closest_point(Vec2::ZERO), None);
+    }
+
+    #[test]
+    fn contains_point() {
+        assert!(square().contains_point(Vec2::new(5., 5.)));
+        assert!(!square().contains_point(Vec2::new(15., 5.)));
+        assert!(!square().contains_point(Vec2::new(5., -1.)));
+    }
+
+    #[test]
+    fn signed_area_follows_winding() {
+        assert_eq!(square().signed_area(), 100.);
+        let clockwise = LineGroup::generate_continuous_closed(vec![
+            Vec2::new(0., 0.),
+            Vec2::new(0., 10.),
+            Vec2::new(10., 10.),
+            Vec2::new(10., 0.),
+        ]);
+        assert_eq!(clockwise.signed_area(), -100.);
+        assert_eq!(clockwise.area(), 100.);
+    }
+
+    #[test]
+    fn perimeter() {
+        assert_eq!(square().perimeter(), 40.);
+    }
+
+    #[test]
+    fn closed_centroid() {
+        assert_eq!(square().centroid(), Some(Vec2::new(5., 5.)));
+        let moved = square().offset(Vec2::new(100., -50.));
+        assert_eq!(moved.centroid(), Some(Vec2::new(105., -45.)));
+    }
+
+    #[test]
+    fn open_centroid_moves_with_the_group() {
+        let open = LineGroup::generate_continuous(vec![
+            Vec2::new(0., 0.),
+            Vec2::new(10., 0.),
+            Vec2::new(10., 10.),
+        ]);
+        let offset = Vec2::new(100., -50.);
+        let centroid = open.centroid().unwrap();
+        assert_eq!(centroid, Vec2::new(7.5, 2.5));
+        assert!(open
+            .offset(offset)
+            .centroid()
+            .unwrap()
+            .abs_diff_eq(centroid + offset, 1e-4));
+    }
+}
@@ -1,9 +1,66 @@
+use std::hash::{Hash, Hasher};
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Lengths are in the same units as the line's points and follow `LineGroup::scaled`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum LineStyle {
+    #[default]
+    Solid,
+    /// One dash and one gap per `pattern_length`.
+    Dashed { pattern_length: f32 },
+    /// One round dot every `spacing`.
+    Dotted { spacing: f32 },
+    /// Width multipliers at `a` and at `b`, interpolated in between.
+    Tapered { start: f32, end: f32 },
+}
+
+impl LineStyle {
+    pub fn scaled(&self, factor: f32) -> LineStyle {
+        match *self {
+            LineStyle::Dashed { pattern_length } => LineStyle::Dashed {
+                pattern_length: pattern_length * factor,
+            },
+            LineStyle::Dotted { spacing } => LineStyle::Dotted {
+                spacing: spacing * factor,
+            },
+            style => style,
+        }
+    }
+
+    /// Packs the style as `(kind, param, param, 0)` for the shader.
+    pub fn as_vec4(&self) -> Vec4 {
+        match *self {
+            LineStyle::Solid => Vec4::ZERO,
+            LineStyle::Dashed { pattern_length } => Vec4::new(1., pattern_length, 0., 0.),
+            LineStyle::Dotted { spacing } => Vec4::new(2., spacing, 0., 0.),
+            LineStyle::Tapered { start, end } => Vec4::new(3., start, end, 0.),
+        }
+    }
+
+    pub fn from_vec4(style: Vec4) -> LineStyle {
+        match style.x as u32 {
+            1 => LineStyle::Dashed {
+                pattern_length: style.y,
+            },
+            2 => LineStyle::Dotted { spacing: style.y },
+            3 => LineStyle::Tapered {
+                start: style.y,
+                end: style.z,
+            },
+            _ => LineStyle::Solid,
+        }
+    }
+}
 
 #[derive(Copy, Clone, Debug)]
 pub struct Line {
     pub(crate) a: Vec2,
-    pub(crate) b: Vec2
+    pub(crate) b: Vec2,
+    pub(crate) color: Color,
+    pub(crate) intensity: f32,
+    pub(crate) style: LineStyle,
 }
 
 impl Line {
@@ -11,10 +68,94 @@ impl Line {
         Line {
             a,
             b,
+            color: Color::WHITE,
+            intensity: 1.,
+            style: LineStyle::Solid,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Line {
+        self.color = color;
+        self
+    }
+
+    /// Values above 1 push the line into HDR range so it picks up the camera bloom.
+    pub fn with_intensity(mut self, intensity: f32) -> Line {
+        self.intensity = intensity;
+        self
+    }
+
+    pub fn with_style(mut self, style: LineStyle) -> Line {
+        self.style = style;
+        self
+    }
+
+    /// Moves both ends of the line while keeping its color, intensity and style.
+    pub fn map_points(&self, f: impl Fn(Vec2) -> Vec2) -> Line {
+        Line {
+            a: f(self.a),
+            b: f(self.b),
+            ..*self
+        }
+    }
+
+    pub fn scaled(&self, factor: f32) -> Line {
+        Line {
+            style: self.style.scaled(factor),
+            ..self.map_points(|p| p * factor)
         }
     }
 
     pub fn as_vec4(&self) -> Vec4 {
         Vec4::new(self.a.x, self.a.y, self.b.x, self.b.y)
     }
-}
\ No newline at end of file
+
+    pub fn color_as_vec4(&self) -> Vec4 {
+        let color = self.color.to_linear();
+        Vec4::new(
+            color.red * self.intensity,
+            color.green * self.intensity,
+            color.blue * self.intensity,
+            color.alpha,
+        )
+    }
+}
+
+impl Line {
+    /// Bits of everything that changes how the line is drawn, shared by `Hash` and `PartialEq`
+    /// so groups can key caches.
+    fn bits(&self) -> [u32; 13] {
+        let color = self.color.to_linear();
+        let style = self.style.as_vec4();
+        [
+            self.a.x,
+            self.a.y,
+            self.b.x,
+            self.b.y,
+            color.red,
+            color.green,
+            color.blue,
+            color.alpha,
+            self.intensity,
+            style.x,
+            style.y,
+            style.z,
+            style.w,
+        ]
+        .map(f32::to_bits)
+    }
+}
+
+impl Hash for Line {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+impl PartialEq for Line {
+    fn eq(&self, other: &Line) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for Line {}
@@ -2,14 +2,21 @@ use avian2d::math::PI;
 use bevy::{
     math::bounding::{Aabb2d, BoundingVolume},
     prelude::*,
+    utils::hashbrown::HashSet,
 };
 use itertools::Itertools;
+use rand::Rng;
 
 use crate::rand::{random_range, random_vec2_range};
 
-use super::line::Line;
+use super::{
+    font::Glyph,
+    line::{Line, LineStyle},
+    simplify::point_key,
+    text::TextLayout,
+};
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
 pub struct LineGroup {
     lines: Vec<Line>,
 }
@@ -40,16 +47,14 @@ impl LineGroup {
         points
     }
 
+    /// Points in the order they are visited when following the chains of the group.
     pub fn to_unique_points(&self) -> Vec<Vec2> {
-        let mut points = Vec::new();
-        for line in self.lines.iter() {
-            for point in [line.a, line.b] {
-                if !points.contains(&point) {
-                    points.push(point);
-                }
-            }
-        }
-        points
+        let mut seen = HashSet::new();
+        self.chains()
+            .iter()
+            .flat_map(|chain| chain.to_points())
+            .filter(|point| seen.insert(point_key(*point)))
+            .collect()
     }
 
     pub fn to_unique_points_looped(&self) -> Vec<Vec2> {
@@ -93,11 +98,16 @@ impl LineGroup {
         LineGroup::generate_continuous_closed(points)
     }
 
-    pub fn generate_random_circle(radius: f32, resolution: u32, range: f32) -> LineGroup {
+    pub fn generate_random_circle(
+        rng: &mut impl Rng,
+        radius: f32,
+        resolution: u32,
+        range: f32,
+    ) -> LineGroup {
         let points = generate_circle_points(radius, resolution);
         let offset_points: Vec<Vec2> = points
             .iter()
-            .map(|p| p + random_vec2_range(-range..range))
+            .map(|p| p + random_vec2_range(rng, -range..range))
             .collect();
         LineGroup::generate_continuous_closed(offset_points)
     }
@@ -116,7 +126,7 @@ impl LineGroup {
     pub fn scaled(&self, factor: f32) -> LineGroup {
         let mut res = LineGroup::default();
         for line in &self.lines {
-            res.add_line(Line::new(line.a * factor, line.b * factor));
+            res.add_line(line.scaled(factor));
         }
         res
     }
@@ -126,7 +136,7 @@ impl LineGroup {
         let vec = Vec2::from_angle(rot);
         let mut res = LineGroup::default();
         for line in &self.lines {
-            res.add_line(Line::new(vec.rotate(line.a), vec.rotate(line.b)));
+            res.add_line(line.map_points(|p| vec.rotate(p)));
         }
         res
     }
@@ -134,7 +144,7 @@ impl LineGroup {
     pub fn offset(&self, offset: Vec2) -> LineGroup {
         let mut res = LineGroup::default();
         for line in &self.lines {
-            res.add_line(Line::new(line.a + offset, line.b + offset));
+            res.add_line(line.map_points(|p| p + offset));
         }
         res
     }
@@ -143,26 +153,49 @@ impl LineGroup {
         let lines = self
             .lines
             .iter()
-            .map(|l| Line::new(Vec2::new(l.a.x, -l.a.y), Vec2::new(l.b.x, -l.b.y)))
+            .map(|l| l.map_points(|p| Vec2::new(p.x, -p.y)))
             .collect();
         LineGroup::new(lines)
     }
 
+    pub fn with_color(&self, color: Color) -> LineGroup {
+        LineGroup::new(self.lines.iter().map(|l| l.with_color(color)).collect())
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> LineGroup {
+        LineGroup::new(self.lines.iter().map(|l| l.with_style(style)).collect())
+    }
+
+    pub fn with_intensity(&self, intensity: f32) -> LineGroup {
+        LineGroup::new(
+            self.lines
+                .iter()
+                .map(|l| l.with_intensity(intensity))
+                .collect(),
+        )
+    }
+
     pub fn centered(&self) -> LineGroup {
         self.offset(-self.calculate_bounding_box().center())
     }
 
-    pub fn scatter(self, range: f32, number: u32, randomize_rotation: bool) -> LineGroup {
+    pub fn scatter(
+        self,
+        rng: &mut impl Rng,
+        range: f32,
+        number: u32,
+        randomize_rotation: bool,
+    ) -> LineGroup {
         fn generate_new_offset(
+            rng: &mut impl Rng,
             previous_offsets: &Vec<Vec2>,
             range: f32,
             min_dist: f32,
         ) -> Option<Vec2> {
             'main: for _ in 0..64 {
-                let offset = Circle::new(range).sample_interior(&mut rand::thread_rng());
+                let offset = Circle::new(range).sample_interior(rng);
                 for previous in previous_offsets {
                     if offset.distance(*previous) < min_dist {
-                        println!("invalid");
                         continue 'main;
                     }
                 }
@@ -176,11 +209,11 @@ impl LineGroup {
         let mut previous_offsets = Vec::new();
         for _ in 0..number {
             let Some(offset): Option<Vec2> =
-                generate_new_offset(&previous_offsets, range, bounding_size)
+                generate_new_offset(rng, &previous_offsets, range, bounding_size)
             else {
                 continue;
             };
-            let rotation: f32 = random_range(0.0..360.);
+            let rotation: f32 = random_range(rng, 0.0..360.);
             let mut instance = self.clone();
             if randomize_rotation {
                 instance = instance.rotated(rotation);
@@ -193,6 +226,7 @@ impl LineGroup {
 
     pub fn scatter_circle(
         &self,
+        rng: &mut impl Rng,
         radius: f32,
         number: u32,
         offset_range: f32,
@@ -202,8 +236,8 @@ impl LineGroup {
         let mut previous_offsets = Vec::new();
         for n in 0..number {
             let circle_pos: Vec2 = sample_circle(n as f32 / number as f32) * radius;
-            let offset = circle_pos + random_vec2_range(-offset_range..offset_range);
-            let rotation: f32 = random_range(0.0..360.);
+            let offset = circle_pos + random_vec2_range(rng, -offset_range..offset_range);
+            let rotation: f32 = random_range(rng, 0.0..360.);
             let mut instance = self.clone();
             if randomize_rotation {
                 instance = instance.rotated(rotation);
@@ -215,133 +249,12 @@ impl LineGroup {
     }
 
     pub fn letter(letter: char) -> LineGroup {
-        match letter {
-            'A' => LineGroup::generate_continuous(vec![
-                Vec2::new(-4., -4.),
-                Vec2::new(0., 4.),
-                Vec2::new(4., -4.),
-            ])
-            .concat(LineGroup::new(vec![Line::new(
-                Vec2::new(-3., 0.),
-                Vec2::new(3., 0.),
-            )])),
-            'E' => LineGroup::generate_continuous(vec![
-                Vec2::new(3., -4.),
-                Vec2::new(-4., -4.),
-                Vec2::new(-4., 4.),
-                Vec2::new(3., 4.),
-            ])
-            .concat(LineGroup::from_line(Line::new(
-                Vec2::new(-4., 0.),
-                Vec2::new(2., 0.),
-            ))),
-            'F' => LineGroup::generate_continuous(vec![
-                Vec2::new(-4., -4.),
-                Vec2::new(-4., 4.),
-                Vec2::new(3., 4.),
-            ])
-            .concat(LineGroup::from_line(Line::new(
-                Vec2::new(-4., 0.),
-                Vec2::new(2., 0.),
-            ))),
-            'G' => LineGroup::generate_continuous(vec![
-                Vec2::new(4., 4.),
-                Vec2::new(-4., 3.),
-                Vec2::new(-4., -3.),
-                Vec2::new(4., -4.),
-                Vec2::new(4., 0.),
-                Vec2::new(0., 0.),
-            ]),
-            'I' => LineGroup::from_line(Line::new(Vec2::new(0., -4.), Vec2::new(0., 4.))),
-            'L' => LineGroup::generate_continuous(vec![
-                Vec2::new(-4., 4.),
-                Vec2::new(-4., -4.),
-                Vec2::new(2., -4.),
-            ]),
-            'M' => LineGroup::generate_continuous(vec![
-                Vec2::new(-4., -4.),
-                Vec2::new(-2., 4.),
-                Vec2::new(0., 0.),
-                Vec2::new(2., 4.),
-                Vec2::new(4., -4.),
-            ]),
-            'N' => LineGroup::generate_continuous(vec![
-                Vec2::new(-4., -4.),
-                Vec2::new(-4., 4.),
-                Vec2::new(4., -4.),
-                Vec2::new(4., 4.),
-            ]),
-            'O' => LineGroup::generate_continuous_closed(vec![
-                Vec2::new(-2., -4.),
-                Vec2::new(2., -4.),
-                Vec2::new(4., 0.),
-                Vec2::new(2., 4.),
-                Vec2::new(-2., 4.),
-                Vec2::new(-4., 0.),
-            ]),
-            'P' => LineGroup::generate_continuous(vec![
-                Vec2::new(-3., -4.),
-                Vec2::new(-3., 4.),
-                Vec2::new(2., 4.),
-                Vec2::new(3., 2.),
-                Vec2::new(2., 0.),
-                Vec2::new(-3., 0.),
-            ]),
-            'R' => LineGroup::generate_continuous(vec![
-                Vec2::new(-3., -4.),
-                Vec2::new(-3., 4.),
-                Vec2::new(2., 4.),
-                Vec2::new(3., 2.),
-                Vec2::new(2., 0.),
-                Vec2::new(-3., 0.),
-                Vec2::new(3., -4.),
-            ]),
-            'S' => LineGroup::generate_continuous(vec![
-                Vec2::new(3., 4.),
-                Vec2::new(-4., 4.),
-                Vec2::new(-4., 0.),
-                Vec2::new(3., 0.),
-                Vec2::new(3., -4.),
-                Vec2::new(-4., -4.),
-            ]),
-            'T' => LineGroup::new(vec![
-                Line::new(Vec2::new(0., -4.), Vec2::new(0., 4.)),
-                Line::new(Vec2::new(-4., 4.), Vec2::new(4., 4.)),
-            ]),
-            'U' => LineGroup::generate_continuous(vec![
-                Vec2::new(-4., 4.),
-                Vec2::new(-4., -2.),
-                Vec2::new(-2., -4.),
-                Vec2::new(2., -4.),
-                Vec2::new(4., -2.),
-                Vec2::new(4., 4.),
-            ]),
-            'V' => LineGroup::generate_continuous(vec![
-                Vec2::new(-3., 4.),
-                Vec2::new(0., -4.),
-                Vec2::new(3., 4.),
-            ]),
-            'Y' => LineGroup::generate_continuous(vec![
-                Vec2::new(-3., 4.),
-                Vec2::new(0., 0.),
-                Vec2::new(3., 4.),
-            ])
-            .concat(LineGroup::from_line(Line::new(
-                Vec2::new(0., 0.),
-                Vec2::new(0., -4.),
-            ))),
-            _ => LineGroup::default(),
-        }
+        Glyph::for_char(letter).lines
     }
 
+    /// Single left aligned line of text, see `TextLayout` for more control.
     pub fn text(text: impl Into<String>) -> LineGroup {
-        let mut res = LineGroup::default();
-        let mut offset = 0.;
-        for c in text.into().chars() {
-            res.extend(LineGroup::letter(c).offset(Vec2::new(offset, 0.)));
-            offset += 10.
-        }
-        res
+        TextLayout::new(text).build()
     }
 }
 
@@ -353,7 +266,38 @@ fn sample_circle(p: f32) -> Vec2 {
 fn generate_circle_points(radius: f32, resolution: u32) -> Vec<Vec2> {
     let mut points = Vec::new();
     for i in 0..resolution {
-        points.push(sample_circle(i as f32 / 8.) * radius)
+        points.push(sample_circle(i as f32 / resolution as f32) * radius)
     }
     points
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circle_points_match_the_resolution() {
+        for resolution in [3, 7, 64] {
+            let points = generate_circle_points(5., resolution);
+            assert_eq!(points.len(), resolution as usize);
+            assert!(points.iter().all(|p| (p.length() - 5.).abs() < 1e-4));
+            let circle = LineGroup::generate_circle(5., resolution);
+            assert_eq!(circle.get_lines().len(), resolution as usize);
+        }
+    }
+
+    #[test]
+    fn unique_points_follow_the_chain() {
+        let points: Vec<Vec2> = (0..4)
+            .map(|i| Vec2::new(i as f32, (i % 2) as f32))
+            .collect();
+        let group = LineGroup::new(vec![
+            Line::new(points[1], points[2]),
+            Line::new(points[3], points[2]),
+            Line::new(points[0], points[1]),
+        ]);
+        assert_eq!(group.to_unique_points(), points);
+        let square = LineGroup::generate_circle(1., 4);
+        assert_eq!(square.to_unique_points(), generate_circle_points(1., 4));
+    }
+}
@@ -1,6 +1,15 @@
-use bevy::prelude::*;
+use std::borrow::Cow;
 
-use super::{line_group::LineGroup, LineRendererMaterial, LineRendererSettings, LineRendererWidth};
+use bevy::{
+    ecs::system::SystemParam, prelude::*, render::storage::ShaderStorageBuffer,
+    utils::hashbrown::HashMap,
+};
+
+use super::{
+    culling::{LineCulling, LineCullingSettings},
+    line_group::LineGroup,
+    LineAnimation, LineRendererMaterial, LineRendererSettings, LineRendererWidth, LineWidth,
+};
 
 #[derive(Component)]
 pub struct LineMesh(pub LineGroup);
@@ -9,48 +18,202 @@ pub struct LineMeshPlugin;
 
 impl Plugin for LineMeshPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, on_change);
+        app.init_resource::<LineMeshCache>().add_systems(
+            Update,
+            (rebind_updated_materials, on_change, release_removed).chain(),
+        );
     }
 }
 
+struct CachedLineMesh {
+    mesh: Handle<Mesh>,
+    material: Handle<LineRendererMaterial>,
+    users: u32,
+}
+
+/// Everything a cached mesh is built from. Compared bit for bit, so two shapes whose hashes
+/// collide still get their own mesh.
+#[derive(Clone, Hash, PartialEq, Eq)]
+struct LineMeshKey {
+    lines: LineGroup,
+    line_width: LineWidth,
+    animation: LineAnimation,
+}
+
+/// Shares mesh and material handles between entities drawing identical line groups.
+/// Entries are dropped, and their assets freed, once no entity uses them anymore.
+#[derive(Resource, Default)]
+pub struct LineMeshCache {
+    entries: HashMap<LineMeshKey, CachedLineMesh>,
+    entities: HashMap<Entity, LineMeshKey>,
+    /// Materials whose buffers were rewritten in place this frame, see `rebind_updated_materials`.
+    rebind: Vec<Handle<LineRendererMaterial>>,
+}
+
+impl LineMeshCache {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn release(&mut self, entity: Entity) -> Option<CachedLineMesh> {
+        let key = self.entities.remove(&entity)?;
+        let entry = self.entries.get_mut(&key)?;
+        entry.users -= 1;
+        if entry.users > 0 {
+            return None;
+        }
+        self.entries.remove(&key)
+    }
+}
+
+/// `LineMesh` entities whose mesh or material needs rebuilding.
+type ChangedLineMeshQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static LineMesh,
+        Option<&'static LineWidth>,
+        Option<&'static LineAnimation>,
+        Option<&'static LineCulling>,
+    ),
+    Or<(
+        Changed<LineMesh>,
+        Changed<LineWidth>,
+        Changed<LineAnimation>,
+        Changed<LineCulling>,
+    )>,
+>;
+
+/// Assets written when a `LineMesh` gets (re)built.
+#[derive(SystemParam)]
+pub struct LineMeshAssets<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<LineRendererMaterial>>,
+    buffers: ResMut<'w, Assets<ShaderStorageBuffer>>,
+}
+
 pub fn on_change(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut line_materials: ResMut<Assets<LineRendererMaterial>>,
-    line_q: Query<(Entity, &LineMesh), Changed<LineMesh>>,
-    line_width: Res<LineRendererWidth>,
+    mut cache: ResMut<LineMeshCache>,
+    mut assets: LineMeshAssets,
+    line_q: ChangedLineMeshQuery,
+    default_width: Res<LineRendererWidth>,
+    culling_settings: Res<LineCullingSettings>,
 ) {
-    for (entity, line) in line_q.iter() {
+    for (entity, line, maybe_width, maybe_animation, maybe_culling) in line_q.iter() {
+        // Nothing to draw, an empty group doesn't even have bounds to build a mesh from.
+        if line.0.get_lines().is_empty() || maybe_culling.is_some_and(|culling| culling.culled) {
+            cache.release(entity);
+            if let Some(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.remove::<(Mesh2d, MeshMaterial2d<LineRendererMaterial>)>();
+            }
+            continue;
+        }
+        let line_width = maybe_width.copied().unwrap_or(default_width.0);
+        let animation = maybe_animation.copied().unwrap_or_default();
+        let lines = match maybe_culling {
+            Some(culling) if culling.lod => {
+                Cow::Owned(line.0.simplified(culling.lod_tolerance(&culling_settings)))
+            }
+            _ => Cow::Borrowed(&line.0),
+        };
+        let key = LineMeshKey {
+            lines: lines.into_owned(),
+            line_width,
+            animation,
+        };
+        if cache.entities.get(&entity) == Some(&key) {
+            continue;
+        }
+        let freed = cache.release(entity);
+        if !cache.entries.contains_key(&key) {
+            let (rectangle, settings) = lines_to_geometry(&key.lines, line_width);
+            let settings = settings.with_animation(animation);
+            let entry = match freed {
+                Some(entry) => {
+                    assets.meshes.insert(&entry.mesh, rectangle.into());
+                    if let Some(material) = assets.materials.get_mut(&entry.material) {
+                        material.update(settings, &mut assets.buffers);
+                        if !cache.rebind.contains(&entry.material) {
+                            cache.rebind.push(entry.material.clone());
+                        }
+                    }
+                    entry
+                }
+                None => CachedLineMesh {
+                    mesh: assets.meshes.add(rectangle),
+                    material: assets
+                        .materials
+                        .add(LineRendererMaterial::new(settings, &mut assets.buffers)),
+                    users: 0,
+                },
+            };
+            cache.entries.insert(key.clone(), entry);
+        }
+        let entry = cache.entries.get_mut(&key).unwrap();
+        entry.users += 1;
+        let mesh_bundle = (
+            Mesh2d(entry.mesh.clone()),
+            MeshMaterial2d(entry.material.clone()),
+        );
+        cache.entities.insert(entity, key);
         let Some(mut entity_commands) = commands.get_entity(entity) else {
             continue;
         };
-        let mesh_bundle = lines_to_mesh(&line.0, line_width.0, &mut meshes, &mut line_materials);
         entity_commands.try_insert(mesh_bundle);
     }
 }
 
-pub fn lines_to_mesh(
+/// Works around Bevy 0.15 preparing `PreparedMaterial2d` and `GpuShaderStorageBuffer` in the same
+/// render set with no ordering between them (`Material2dPlugin` registers its `RenderAssetPlugin`
+/// without an `AFTER` dependency). When the material goes first, its bind group keeps pointing
+/// at the previous GPU buffers, and nothing rebuilds it once the new ones are uploaded. Marking
+/// the material modified one frame later rebuilds it against the uploaded buffers. Only
+/// materials `on_change` actually rewrote are touched, and each of them once.
+fn rebind_updated_materials(
+    mut cache: ResMut<LineMeshCache>,
+    mut materials: ResMut<Assets<LineRendererMaterial>>,
+) {
+    for material in cache.rebind.drain(..) {
+        materials.get_mut(&material);
+    }
+}
+
+fn release_removed(mut cache: ResMut<LineMeshCache>, mut removed: RemovedComponents<LineMesh>) {
+    for entity in removed.read() {
+        cache.release(entity);
+    }
+}
+
+pub fn lines_to_geometry(
     lines: &LineGroup,
-    line_width: f32,
-    meshes: &mut Assets<Mesh>,
-    line_materials: &mut Assets<LineRendererMaterial>,
-) -> (Mesh2d, MeshMaterial2d<LineRendererMaterial>) {
+    line_width: LineWidth,
+) -> (Rectangle, LineRendererSettings) {
     let padding = 1.2;
     let bounding_size = lines.calculate_bounding_size();
-    let actual_size = bounding_size * padding;
-    let scaling_factor = bounding_size / 100.;
-    let adjusted_width = line_width / scaling_factor;
-    let adjusted_lines = lines.scaled((1. / bounding_size) * (1. / padding));
+    // Screen widths are unknown in world units here, only the relative padding covers them.
+    let width_padding = match line_width {
+        LineWidth::World(width) => width,
+        LineWidth::Screen(_) => 0.,
+    };
+    let actual_size = bounding_size * padding + width_padding;
+    let adjusted_lines = lines.scaled(1. / actual_size);
     let flipped_lines = adjusted_lines.flipped_vertically();
+    let settings = match line_width {
+        LineWidth::World(width) => LineRendererSettings::new(flipped_lines, width / actual_size),
+        LineWidth::Screen(pixels) => {
+            LineRendererSettings::new(flipped_lines, pixels).in_screen_space()
+        }
+    };
     (
-        Mesh2d(meshes.add(Rectangle {
+        Rectangle {
             half_size: Vec2::splat(actual_size),
-        })),
-        MeshMaterial2d(
-            line_materials.add(LineRendererMaterial::new(LineRendererSettings::new(
-                flipped_lines,
-                adjusted_width,
-            ))),
-        ),
+        },
+        settings,
     )
 }
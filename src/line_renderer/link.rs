@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+
+use super::{
+    line::{Line, LineStyle},
+    line_group::LineGroup,
+    line_mesh::{on_change, LineMesh},
+};
+
+/// Largest distance between a bent link and its lines, in world units.
+const LINK_TOLERANCE: f32 = 0.5;
+
+pub struct LineLinkPlugin;
+
+impl Plugin for LineLinkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_links.before(on_change));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineLinkShape {
+    Straight,
+    /// Bows sideways by `bend` times the distance between the ends, to the left going from
+    /// `from` to `to`, negative to the right.
+    Curved {
+        bend: f32,
+    },
+    /// Rope of a fixed `length` sagging to the left once the ends get closer than it.
+    Slack {
+        length: f32,
+    },
+}
+
+/// Draws a line between two entities, following them every frame.
+/// The link's own `Transform` is overwritten, it despawns once either end is gone.
+#[derive(Component, Clone, Debug)]
+#[require(Transform)]
+pub struct LineLink {
+    pub from: Entity,
+    pub to: Entity,
+    pub shape: LineLinkShape,
+    pub color: Color,
+    pub intensity: f32,
+    pub style: LineStyle,
+    /// `to - from` the mesh was last built for, moving both ends together only moves the link.
+    drawn_delta: Option<Vec2>,
+}
+
+impl LineLink {
+    pub fn new(from: Entity, to: Entity) -> LineLink {
+        LineLink {
+            from,
+            to,
+            shape: LineLinkShape::Straight,
+            color: Color::WHITE,
+            intensity: 1.,
+            style: LineStyle::Solid,
+            drawn_delta: None,
+        }
+    }
+
+    pub fn with_shape(&self, shape: LineLinkShape) -> LineLink {
+        let mut new = self.clone();
+        new.shape = shape;
+        new
+    }
+
+    pub fn with_color(&self, color: Color) -> LineLink {
+        let mut new = self.clone();
+        new.color = color;
+        new
+    }
+
+    pub fn with_intensity(&self, intensity: f32) -> LineLink {
+        let mut new = self.clone();
+        new.intensity = intensity;
+        new
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> LineLink {
+        let mut new = self.clone();
+        new.style = style;
+        new
+    }
+
+    /// Lines from `-delta / 2` to `delta / 2`, `delta` going from `from` to `to`.
+    pub fn to_line_group(&self, delta: Vec2) -> LineGroup {
+        let (start, end) = (-delta / 2., delta / 2.);
+        let distance = delta.length();
+        let sag = match self.shape {
+            LineLinkShape::Straight => 0.,
+            LineLinkShape::Curved { bend } => bend * distance,
+            // Parabola arc length is about `distance + 8 sag² / (3 distance)`.
+            LineLinkShape::Slack { length } if length > distance => {
+                (3. * distance * (length - distance) / 8.).sqrt()
+            }
+            LineLinkShape::Slack { .. } => 0.,
+        };
+        let lines = if distance == 0. {
+            LineGroup::default()
+        } else if sag == 0. {
+            LineGroup::from_line(Line::new(start, end))
+        } else {
+            // A quadratic bezier's middle only goes half way to its control point.
+            let control = delta.perp().normalize() * sag * 2.;
+            LineGroup::generate_quadratic_bezier(start, control, end, LINK_TOLERANCE)
+        };
+        lines
+            .with_color(self.color)
+            .with_intensity(self.intensity)
+            .with_style(self.style)
+    }
+}
+
+fn update_links(
+    mut commands: Commands,
+    mut link_q: Query<(Entity, &mut LineLink, &mut Transform)>,
+    end_q: Query<&GlobalTransform>,
+) {
+    for (entity, mut link, mut transform) in link_q.iter_mut() {
+        let (Ok(from), Ok(to)) = (end_q.get(link.from), end_q.get(link.to)) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        let (from, to) = (from.translation().xy(), to.translation().xy());
+        let middle = (from + to) / 2.;
+        transform.translation = middle.extend(transform.translation.z);
+        let delta = to - from;
+        if !link.is_changed() && link.drawn_delta == Some(delta) {
+            continue;
+        }
+        link.bypass_change_detection().drawn_delta = Some(delta);
+        commands
+            .entity(entity)
+            .insert(LineMesh(link.to_line_group(delta)));
+    }
+}
@@ -1,28 +1,69 @@
-use bevy::{prelude::*, render::render_resource::*, sprite::*};
-use line::Line;
+use std::hash::{Hash, Hasher};
+
+use asset::LineArtPlugin;
+use bevy::{
+    prelude::*,
+    render::{render_resource::*, storage::ShaderStorageBuffer},
+    sprite::*,
+};
+use bins::{LineBins, TILE_GRID};
+use culling::LineCullingPlugin;
+use debug::DebugLinesPlugin;
+use line::{Line, LineStyle};
 use line_group::LineGroup;
 use line_mesh::LineMeshPlugin;
+use link::LineLinkPlugin;
+use morph::LineMorphPlugin;
+use trail::LineTrailPlugin;
 
+pub mod asset;
+pub mod bins;
+pub mod collider;
+pub mod culling;
+pub mod curve;
+pub mod debug;
+pub mod font;
+pub mod geometry;
 pub mod line;
 pub mod line_group;
 pub mod line_mesh;
-
-const LINE_NUMBER: usize = 256;
+pub mod link;
+pub mod morph;
+pub mod raster;
+pub mod simplify;
+pub mod slice;
+pub mod svg;
+pub mod text;
+pub mod trail;
 
 pub struct LineRendererPlugin;
 
 impl Plugin for LineRendererPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(LineRendererWidth(0.01))
+        app.insert_resource(LineRendererWidth(LineWidth::World(1.2)))
             .add_plugins(Material2dPlugin::<LineRendererMaterial>::default())
-            .add_plugins(LineMeshPlugin);
+            .add_plugins((
+                LineMeshPlugin,
+                LineMorphPlugin,
+                LineArtPlugin,
+                DebugLinesPlugin,
+                LineTrailPlugin,
+                LineCullingPlugin,
+                LineLinkPlugin,
+            ));
     }
 }
 
 #[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
 pub struct LineRendererMaterial {
     #[uniform(0)]
-    pub settings: LineRendererSettings,
+    pub uniform: LineRendererUniform,
+    #[storage(1, read_only)]
+    pub lines: Handle<ShaderStorageBuffer>,
+    #[storage(2, read_only)]
+    pub tiles: Handle<ShaderStorageBuffer>,
+    #[storage(3, read_only)]
+    pub tile_lines: Handle<ShaderStorageBuffer>,
 }
 
 impl Material2d for LineRendererMaterial {
@@ -36,43 +77,294 @@ impl Material2d for LineRendererMaterial {
 }
 
 impl LineRendererMaterial {
-    pub fn new(settings: LineRendererSettings) -> LineRendererMaterial {
-        LineRendererMaterial { settings }
+    pub fn new(
+        settings: LineRendererSettings,
+        buffers: &mut Assets<ShaderStorageBuffer>,
+    ) -> LineRendererMaterial {
+        let (tiles, tile_lines) = settings.to_tile_buffers();
+        LineRendererMaterial {
+            uniform: settings.to_uniform(),
+            lines: buffers.add(settings.to_storage_buffer()),
+            tiles: buffers.add(tiles),
+            tile_lines: buffers.add(tile_lines),
+        }
+    }
+
+    /// Rewrites the buffers behind the existing handles, so shapes re-meshed every frame don't
+    /// churn through buffer assets. See `LineMeshCache` for getting the bind group rebuilt.
+    pub fn update(
+        &mut self,
+        settings: LineRendererSettings,
+        buffers: &mut Assets<ShaderStorageBuffer>,
+    ) {
+        let (tiles, tile_lines) = settings.to_tile_buffers();
+        self.uniform = settings.to_uniform();
+        buffers.insert(&self.lines, settings.to_storage_buffer());
+        buffers.insert(&self.tiles, tiles);
+        buffers.insert(&self.tile_lines, tile_lines);
     }
 }
 
 #[derive(ShaderType, Debug, Clone)]
+pub struct LineRendererUniform {
+    pub width: f32,
+    pub screen_space: u32,
+    pub total_length: f32,
+    pub pulse_frequency: f32,
+    pub pulse_amplitude: f32,
+    pub reveal_fraction: f32,
+    pub reveal_duration: f32,
+    pub reveal_start_time: f32,
+    pub tile_grid: u32,
+}
+
+/// `style.x` of a `LineRendererLine`, same as in `lines.wgsl`.
+pub(crate) const STYLE_DASHED: u32 = 1;
+pub(crate) const STYLE_DOTTED: u32 = 2;
+pub(crate) const STYLE_TAPERED: u32 = 3;
+
+#[derive(ShaderType, Debug, Clone, Copy)]
+pub struct LineRendererLine {
+    pub points: Vec4,
+    pub color: Vec4,
+    pub style: Vec4,
+    /// Length of the path drawn by the previous lines of the group.
+    pub path_start: f32,
+}
+
+impl LineRendererLine {
+    pub fn new(line: &Line, path_start: f32) -> LineRendererLine {
+        LineRendererLine {
+            points: line.as_vec4(),
+            color: line.color_as_vec4(),
+            style: line.style.as_vec4(),
+            path_start,
+        }
+    }
+}
+
+/// Shader side animation of a `LineMesh`, driven by the global time so nothing is re-meshed per frame.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct LineAnimation {
+    pub pulse_frequency: f32,
+    pub pulse_amplitude: f32,
+    /// Part of the path's total length that gets drawn, following the group's line order.
+    pub reveal_fraction: f32,
+    /// When above 0, the reveal goes from 0 to `reveal_fraction` over this many seconds.
+    pub reveal_duration: f32,
+    /// In the wrapped elapsed time used by the shader, see `Time::elapsed_secs_wrapped`.
+    pub reveal_start_time: f32,
+}
+
+impl Default for LineAnimation {
+    fn default() -> Self {
+        LineAnimation {
+            pulse_frequency: 0.,
+            pulse_amplitude: 0.,
+            reveal_fraction: 1.,
+            reveal_duration: 0.,
+            reveal_start_time: 0.,
+        }
+    }
+}
+
+impl LineAnimation {
+    pub fn pulse(frequency: f32, amplitude: f32) -> LineAnimation {
+        LineAnimation {
+            pulse_frequency: frequency,
+            pulse_amplitude: amplitude,
+            ..default()
+        }
+    }
+
+    pub fn reveal(fraction: f32) -> LineAnimation {
+        LineAnimation {
+            reveal_fraction: fraction,
+            ..default()
+        }
+    }
+
+    pub fn reveal_over(start_time: f32, duration: f32) -> LineAnimation {
+        LineAnimation {
+            reveal_duration: duration,
+            reveal_start_time: start_time,
+            ..default()
+        }
+    }
+
+    pub fn with_pulse(&self, frequency: f32, amplitude: f32) -> LineAnimation {
+        let mut new = *self;
+        new.pulse_frequency = frequency;
+        new.pulse_amplitude = amplitude;
+        new
+    }
+}
+
+impl LineAnimation {
+    fn bits(&self) -> [u32; 5] {
+        [
+            self.pulse_frequency,
+            self.pulse_amplitude,
+            self.reveal_fraction,
+            self.reveal_duration,
+            self.reveal_start_time,
+        ]
+        .map(f32::to_bits)
+    }
+}
+
+impl Hash for LineAnimation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+impl PartialEq for LineAnimation {
+    fn eq(&self, other: &LineAnimation) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for LineAnimation {}
+
+#[derive(Debug, Clone)]
 pub struct LineRendererSettings {
-    pub lines: [Vec4; LINE_NUMBER],
+    pub lines: Vec<LineRendererLine>,
     pub width: f32,
+    pub screen_space: bool,
+    pub total_length: f32,
+    pub animation: LineAnimation,
 }
 
 impl LineRendererSettings {
     pub fn new(line_group: LineGroup, width: f32) -> LineRendererSettings {
-        let lines = line_group.get_lines();
-        let line_amount = lines.len();
-        if line_amount > LINE_NUMBER {
-            panic!("Too many lines!")
-        }
-        let mut lines_array: [Line; LINE_NUMBER] = [Line::new(Vec2::ZERO, Vec2::ZERO); LINE_NUMBER];
-        for i in 0..line_amount {
-            lines_array[i] = lines[i];
+        let mut lines = Vec::new();
+        let mut total_length = 0.;
+        for line in line_group.get_lines() {
+            lines.push(LineRendererLine::new(line, total_length));
+            total_length += line.a.distance(line.b);
         }
         LineRendererSettings {
-            lines: lines_array
-                .iter()
-                .map(|l| l.as_vec4())
-                .collect::<Vec<Vec4>>()
-                .try_into()
-                .unwrap(),
+            lines,
             width,
+            screen_space: false,
+            total_length,
+            animation: LineAnimation::default(),
         }
     }
 
+    pub fn with_animation(&self, animation: LineAnimation) -> LineRendererSettings {
+        let mut new = self.clone();
+        new.animation = animation;
+        new
+    }
+
+    /// Interprets `width` as screen pixels instead of uv units.
+    pub fn in_screen_space(&self) -> LineRendererSettings {
+        let mut new = self.clone();
+        new.screen_space = true;
+        new
+    }
+
     pub fn get_lines(&self) -> LineGroup {
-        LineGroup::new(self.lines.map(|l| Line::new(l.xy(), l.zw())).to_vec())
+        LineGroup::new(
+            self.lines
+                .iter()
+                .map(|l| {
+                    let color = LinearRgba::new(l.color.x, l.color.y, l.color.z, l.color.w);
+                    Line::new(l.points.xy(), l.points.zw())
+                        .with_color(color.into())
+                        .with_style(LineStyle::from_vec4(l.style))
+                })
+                .collect(),
+        )
+    }
+
+    pub fn to_uniform(&self) -> LineRendererUniform {
+        LineRendererUniform {
+            width: self.width,
+            screen_space: self.screen_space as u32,
+            total_length: self.total_length,
+            pulse_frequency: self.animation.pulse_frequency,
+            pulse_amplitude: self.animation.pulse_amplitude,
+            reveal_fraction: self.animation.reveal_fraction,
+            reveal_duration: self.animation.reveal_duration,
+            reveal_start_time: self.animation.reveal_start_time,
+            tile_grid: self.tile_grid(),
+        }
+    }
+
+    pub fn to_storage_buffer(&self) -> ShaderStorageBuffer {
+        // Zero-sized storage bindings are invalid, keep one unused slot for empty groups.
+        if self.lines.is_empty() {
+            return ShaderStorageBuffer::from(vec![LineRendererLine {
+                points: Vec4::ZERO,
+                color: Vec4::ZERO,
+                style: Vec4::ZERO,
+                path_start: 0.,
+            }]);
+        }
+        ShaderStorageBuffer::from(self.lines.clone())
+    }
+
+    /// Screen widths are unknown in uv units when meshing, so those quads stay a single tile.
+    pub fn tile_grid(&self) -> u32 {
+        if self.screen_space {
+            1
+        } else {
+            TILE_GRID
+        }
+    }
+
+    pub fn bins(&self) -> LineBins {
+        LineBins::new(&self.lines, self.tile_grid(), self.width)
+    }
+
+    /// Ranges of every tile, then the line indices they point into.
+    pub fn to_tile_buffers(&self) -> (ShaderStorageBuffer, ShaderStorageBuffer) {
+        let mut bins = self.bins();
+        // Same as the lines, tiles with no lines get an unused index so the binding isn't empty.
+        if bins.indices.is_empty() {
+            bins.indices.push(0);
+        }
+        (
+            ShaderStorageBuffer::from(bins.tiles),
+            ShaderStorageBuffer::from(bins.indices),
+        )
     }
 }
 
+/// Half thickness of the lines of a `LineMesh`, independent of the size of its shape.
+/// `World` is in the entity's local units, `Screen` in pixels whatever the camera zoom.
+#[derive(Component, Clone, Copy, Debug)]
+pub enum LineWidth {
+    World(f32),
+    Screen(f32),
+}
+
+impl LineWidth {
+    fn bits(&self) -> (u8, u32) {
+        match self {
+            LineWidth::World(width) => (0, width.to_bits()),
+            LineWidth::Screen(width) => (1, width.to_bits()),
+        }
+    }
+}
+
+impl Hash for LineWidth {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+impl PartialEq for LineWidth {
+    fn eq(&self, other: &LineWidth) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for LineWidth {}
+
+/// Width used by `LineMesh` entities without their own `LineWidth`.
 #[derive(Resource)]
-pub struct LineRendererWidth(f32);
+pub struct LineRendererWidth(pub LineWidth);
@@ -0,0 +1,261 @@
+use bevy::{
+    math::curve::{EaseFunction, EasingCurve},
+    prelude::*,
+};
+
+use super::{
+    line::Line,
+    line_group::LineGroup,
+    line_mesh::{on_change, LineMesh},
+};
+
+const EPSILON: f32 = 1e-5;
+
+pub struct LineMorphPlugin;
+
+impl Plugin for LineMorphPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_morphs.before(on_change));
+    }
+}
+
+/// Tweens the entity's `LineMesh` from one group to another, then removes itself.
+#[derive(Component, Clone, Debug)]
+pub struct LineMeshMorph {
+    from: LineGroup,
+    to: LineGroup,
+    target: LineGroup,
+    duration: f32,
+    elapsed: f32,
+    easing: EaseFunction,
+}
+
+impl LineMeshMorph {
+    pub fn new(from: &LineGroup, to: &LineGroup, duration: f32) -> LineMeshMorph {
+        let (resampled_from, resampled_to) = from.resampled_with(to);
+        LineMeshMorph {
+            from: resampled_from,
+            to: resampled_to,
+            target: to.clone(),
+            duration,
+            elapsed: 0.,
+            easing: EaseFunction::CubicInOut,
+        }
+    }
+
+    pub fn with_easing(&self, easing: EaseFunction) -> LineMeshMorph {
+        let mut new = self.clone();
+        new.easing = easing;
+        new
+    }
+
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0. {
+            return 1.;
+        }
+        (self.elapsed / self.duration).clamp(0., 1.)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.
+    }
+
+    pub fn current(&self) -> LineGroup {
+        if self.is_finished() {
+            return self.target.clone();
+        }
+        let t = EasingCurve::new(0., 1., self.easing).sample_clamped(self.progress());
+        self.from.lerp(&self.to, t)
+    }
+}
+
+impl LineGroup {
+    /// Splits the lines of both groups at the same fractions of their total length, so they end
+    /// up with matching line counts and each pair of lines covers the same stretch of path.
+    /// An empty group is treated as the other one collapsed into its centroid.
+    pub fn resampled_with(&self, other: &LineGroup) -> (LineGroup, LineGroup) {
+        match (self.get_lines().is_empty(), other.get_lines().is_empty()) {
+            (true, true) => return (LineGroup::default(), LineGroup::default()),
+            (true, false) => return (other.collapsed(), other.clone()),
+            (false, true) => return (self.clone(), self.collapsed()),
+            (false, false) => {}
+        }
+        let mut breakpoints = self.path_fractions();
+        breakpoints.extend(other.path_fractions());
+        breakpoints.sort_by(|a, b| a.total_cmp(b));
+        breakpoints.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+        (
+            self.resampled_at(&breakpoints),
+            other.resampled_at(&breakpoints),
+        )
+    }
+
+    /// Interpolates line by line, lines past the shorter group are dropped.
+    pub fn lerp(&self, other: &LineGroup, t: f32) -> LineGroup {
+        LineGroup::new(
+            self.get_lines()
+                .iter()
+                .zip(other.get_lines())
+                .map(|(from, to)| {
+                    Line::new(from.a.lerp(to.a, t), from.b.lerp(to.b, t))
+                        .with_color(from.color.mix(&to.color, t))
+                        .with_intensity(from.intensity.lerp(to.intensity, t))
+                        .with_style(if t < 0.5 { from.style } else { to.style })
+                })
+                .collect(),
+        )
+    }
+
+    fn collapsed(&self) -> LineGroup {
+        let centroid = self.centroid().unwrap_or_default();
+        LineGroup::new(
+            self.get_lines()
+                .iter()
+                .map(|l| l.map_points(|_| centroid))
+                .collect(),
+        )
+    }
+
+    /// Weight of each line along the path, falling back to equal weights for flat groups.
+    fn path_weights(&self) -> Vec<f32> {
+        let lengths: Vec<f32> = self.get_lines().iter().map(|l| l.length()).collect();
+        if lengths.iter().sum::<f32>() < EPSILON {
+            return vec![1.; lengths.len()];
+        }
+        lengths
+    }
+
+    /// Cumulative fractions of the path where each line starts, plus the final 1.
+    fn path_fractions(&self) -> Vec<f32> {
+        let weights = self.path_weights();
+        let total: f32 = weights.iter().sum();
+        let mut fractions = vec![0.];
+        let mut travelled = 0.;
+        for weight in weights {
+            travelled += weight;
+            fractions.push(travelled / total);
+        }
+        fractions
+    }
+
+    fn resampled_at(&self, breakpoints: &[f32]) -> LineGroup {
+        let fractions = self.path_fractions();
+        let lines = self.get_lines();
+        LineGroup::new(
+            breakpoints
+                .windows(2)
+                .map(|w| {
+                    let middle = (w[0] + w[1]) / 2.;
+                    let index = fractions
+                        .windows(2)
+                        .position(|f| middle <= f[1])
+                        .unwrap_or(lines.len() - 1);
+                    let (start, end) = (fractions[index], fractions[index + 1]);
+                    let span = (end - start).max(EPSILON);
+                    let line = lines[index];
+                    let point_at = |fraction: f32| {
+                        line.a
+                            .lerp(line.b, ((fraction - start) / span).clamp(0., 1.))
+                    };
+                    Line {
+                        a: point_at(w[0]),
+                        b: point_at(w[1]),
+                        ..line
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+fn update_morphs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut morph_q: Query<(Entity, &mut LineMeshMorph)>,
+) {
+    for (entity, mut morph) in morph_q.iter_mut() {
+        morph.elapsed += time.delta_secs();
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.try_insert(LineMesh(morph.current()));
+        if morph.is_finished() {
+            entity_commands.remove::<LineMeshMorph>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance_to(group: &LineGroup, point: Vec2) -> f32 {
+        point.distance(group.closest_point(point).unwrap())
+    }
+
+    fn total_length(group: &LineGroup) -> f32 {
+        group.get_lines().iter().map(|l| l.length()).sum()
+    }
+
+    /// Same path: every point of one lies on the other, and both are just as long.
+    fn assert_same_shape(actual: &LineGroup, expected: &LineGroup) {
+        for point in actual.to_points() {
+            assert!(
+                distance_to(expected, point) < 1e-4,
+                "{point} is off the shape"
+            );
+        }
+        for point in expected.to_points() {
+            assert!(distance_to(actual, point) < 1e-4, "{point} is missing");
+        }
+        assert!((total_length(actual) - total_length(expected)).abs() < 1e-3);
+    }
+
+    fn triangle() -> LineGroup {
+        LineGroup::generate_circle(5., 3)
+    }
+
+    fn heptagon() -> LineGroup {
+        LineGroup::generate_circle(8., 7).offset(Vec2::new(2., 1.))
+    }
+
+    #[test]
+    fn resampled_groups_match_in_line_count() {
+        let (from, to) = triangle().resampled_with(&heptagon());
+        assert_eq!(triangle().get_lines().len(), 3);
+        assert_eq!(heptagon().get_lines().len(), 7);
+        assert_eq!(from.get_lines().len(), to.get_lines().len());
+        assert!(from.get_lines().len() >= 7);
+        for step in 0..=10 {
+            let t = step as f32 / 10.;
+            assert_eq!(from.lerp(&to, t).get_lines().len(), from.get_lines().len());
+        }
+    }
+
+    #[test]
+    fn ends_reproduce_both_shapes() {
+        let (from, to) = triangle().resampled_with(&heptagon());
+        assert_same_shape(&from.lerp(&to, 0.), &triangle());
+        assert_same_shape(&from.lerp(&to, 1.), &heptagon());
+    }
+
+    #[test]
+    fn morph_starts_on_the_source_and_ends_on_the_target() {
+        let mut morph = LineMeshMorph::new(&triangle(), &heptagon(), 1.);
+        let count = morph.current().get_lines().len();
+        assert_same_shape(&morph.current(), &triangle());
+        for _ in 0..9 {
+            morph.elapsed += 0.1;
+            assert_eq!(morph.current().get_lines().len(), count);
+        }
+        morph.elapsed = 1.;
+        assert!(morph.is_finished());
+        assert_eq!(morph.current(), heptagon());
+    }
+
+    #[test]
+    fn empty_source_grows_out_of_the_target_centroid() {
+        let (from, to) = LineGroup::default().resampled_with(&heptagon());
+        assert_eq!(from.get_lines().len(), to.get_lines().len());
+        let centroid = heptagon().centroid().unwrap();
+        assert!(from.to_points().iter().all(|p| p.distance(centroid) < 1e-4));
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use image::{ImageError, ImageFormat, Rgba, RgbaImage};
+
+use super::{
+    bins::LineBins, line_group::LineGroup, line_mesh::lines_to_geometry, LineRendererLine,
+    LineRendererSettings, LineWidth, STYLE_DASHED, STYLE_DOTTED, STYLE_TAPERED,
+};
+
+/// CPU copy of `lines.wgsl`, so the output can be checked without a GPU. Animations are left out.
+impl LineGroup {
+    /// Draws the group the way a `LineMesh` of it would look when it covers `size` pixels.
+    pub fn rasterize(&self, size: u32, line_width: LineWidth) -> RgbaImage {
+        if self.get_lines().is_empty() {
+            return RgbaImage::new(size, size);
+        }
+        let (_, settings) = lines_to_geometry(self, line_width);
+        let width = if settings.screen_space {
+            settings.width * 2. / size as f32
+        } else {
+            settings.width
+        };
+        let bins = settings.bins();
+        RgbaImage::from_fn(size, size, |x, y| {
+            let uv = (Vec2::new(x as f32, y as f32) + 0.5) / size as f32;
+            let color = settings.color_at(&bins, uv * 2. - Vec2::ONE, width, settings.total_length);
+            Rgba(Srgba::from(LinearRgba::from_vec4(color)).to_u8_array())
+        })
+    }
+
+    pub fn save_png(
+        &self,
+        path: impl AsRef<Path>,
+        size: u32,
+        line_width: LineWidth,
+    ) -> Result<(), ImageError> {
+        self.rasterize(size, line_width)
+            .save_with_format(path, ImageFormat::Png)
+    }
+}
+
+impl LineRendererSettings {
+    /// Same as `line_color_at` in the shader.
+    fn color_at(
+        &self,
+        bins: &LineBins,
+        centered_uv: Vec2,
+        width: f32,
+        revealed_length: f32,
+    ) -> Vec4 {
+        for index in bins.lines_at(centered_uv) {
+            let line = &self.lines[*index as usize];
+            let (a, b) = (line.points.xy(), line.points.zw());
+            let Some((distance, t)) = distance_to_segment(centered_uv, a, b) else {
+                continue;
+            };
+            let along = t * a.distance(b);
+            if line.path_start + along > revealed_length {
+                continue;
+            }
+            if is_on_styled_line(line, distance, line.path_start + along, t, width) {
+                return line.color;
+            }
+        }
+        Vec4::ZERO
+    }
+}
+
+fn is_on_styled_line(
+    line: &LineRendererLine,
+    distance: f32,
+    along: f32,
+    t: f32,
+    width: f32,
+) -> bool {
+    let style = line.style;
+    match style.x as u32 {
+        STYLE_DASHED => (along / style.y).fract() < 0.5 && distance < width,
+        STYLE_DOTTED => {
+            let to_dot = along - (along / style.y).round() * style.y;
+            Vec2::new(to_dot, distance).length() < width
+        }
+        STYLE_TAPERED => distance < width * style.y.lerp(style.z, t),
+        _ => distance < width,
+    }
+}
+
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> Option<(f32, f32)> {
+    let ab = b - a;
+    let ab_length_squared = ab.dot(ab);
+    if ab_length_squared == 0. {
+        return None;
+    }
+    let t = ((p - a).dot(ab) / ab_length_squared).clamp(0., 1.);
+    Some(((p - (a + t * ab)).length(), t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_renderer::line::{Line, LineStyle};
+
+    fn segment() -> LineGroup {
+        LineGroup::from_line(Line::new(Vec2::new(-10., 0.), Vec2::new(10., 0.)))
+    }
+
+    fn is_drawn(image: &RgbaImage, x: u32, y: u32) -> bool {
+        image.get_pixel(x, y).0[3] > 0
+    }
+
+    #[test]
+    fn world_width() {
+        // Bounds of 10 padded to a half size of 13, so 10 pixels per unit and the line on y = 130.
+        let image = segment().rasterize(260, LineWidth::World(1.));
+        assert_eq!(image.get_pixel(130, 130).0, [255; 4]);
+        assert!(is_drawn(&image, 130, 121));
+        assert!(is_drawn(&image, 130, 139));
+        assert!(!is_drawn(&image, 130, 119));
+        assert!(!is_drawn(&image, 130, 141));
+        assert!(is_drawn(&image, 38, 130));
+        assert!(!is_drawn(&image, 18, 130));
+        assert!(!is_drawn(&image, 240, 130));
+    }
+
+    #[test]
+    fn screen_width() {
+        // No width padding, the line lies between rows 119 and 120 whatever the image size.
+        for size in [240, 480] {
+            let image = segment().rasterize(size, LineWidth::Screen(3.));
+            let middle = size / 2;
+            assert!(is_drawn(&image, middle, middle - 3));
+            assert!(is_drawn(&image, middle, middle + 2));
+            assert!(!is_drawn(&image, middle, middle - 4));
+            assert!(!is_drawn(&image, middle, middle + 3));
+        }
+    }
+
+    #[test]
+    fn empty_group_is_transparent() {
+        let image = LineGroup::default().rasterize(16, LineWidth::World(1.));
+        assert!(image.pixels().all(|pixel| pixel.0 == [0; 4]));
+    }
+
+    #[test]
+    fn dashes_continue_across_segments() {
+        // Segments of about 1 unit are shorter than a dash, only the path position can gap them.
+        let circle = LineGroup::generate_circle(10., 64)
+            .with_style(LineStyle::Dashed { pattern_length: 4. });
+        // Half size of 12.5, so 10 pixels per unit around the center at 125.
+        let image = circle.rasterize(250, LineWidth::World(0.5));
+        let drawn = (0..360)
+            .filter(|degrees| {
+                let angle = (*degrees as f32).to_radians();
+                let pixel = Vec2::splat(125.) + Vec2::new(angle.cos(), -angle.sin()) * 100.;
+                is_drawn(&image, pixel.x as u32, pixel.y as u32)
+            })
+            .count();
+        assert!((120..240).contains(&drawn), "{drawn} of 360 drawn");
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    utils::hashbrown::{HashMap, HashSet},
+};
+
+use super::{
+    line::{Line, LineStyle},
+    line_group::LineGroup,
+};
+
+/// Exact position of a point, with -0 and 0 folded together.
+type PointKey = (u32, u32);
+
+pub(crate) fn point_key(point: Vec2) -> PointKey {
+    ((point.x + 0.).to_bits(), (point.y + 0.).to_bits())
+}
+
+impl Line {
+    /// Same line drawn from `b` to `a`, tapers are swapped to stay at the same end.
+    pub fn reversed(&self) -> Line {
+        let style = match self.style {
+            LineStyle::Tapered { start, end } => LineStyle::Tapered {
+                start: end,
+                end: start,
+            },
+            style => style,
+        };
+        Line {
+            a: self.b,
+            b: self.a,
+            style,
+            ..*self
+        }
+    }
+
+    /// Whether two lines can become one without changing how they are drawn. Tapers vary along
+    /// the line so tapered lines never merge.
+    fn looks_like(&self, other: &Line) -> bool {
+        !matches!(self.style, LineStyle::Tapered { .. })
+            && self.color == other.color
+            && self.intensity == other.intensity
+            && self.style == other.style
+    }
+}
+
+/// Line ends only connect when their points are exactly equal, as they are in groups built from
+/// point lists.
+impl LineGroup {
+    /// Splits the group into runs of touching lines, each oriented head to tail.
+    pub fn chains(&self) -> Vec<LineGroup> {
+        let lines = self.get_lines();
+        let mut ends: HashMap<PointKey, Vec<usize>> = HashMap::new();
+        for (i, line) in lines.iter().enumerate() {
+            ends.entry(point_key(line.a)).or_default().push(i);
+            ends.entry(point_key(line.b)).or_default().push(i);
+        }
+        let mut used = vec![false; lines.len()];
+        let take_from = |point: Vec2, used: &mut Vec<bool>| -> Option<Line> {
+            let index = *ends.get(&point_key(point))?.iter().find(|i| !used[**i])?;
+            used[index] = true;
+            let line = lines[index];
+            Some(if point_key(line.a) == point_key(point) {
+                line
+            } else {
+                line.reversed()
+            })
+        };
+
+        let mut chains = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            if used[i] {
+                continue;
+            }
+            used[i] = true;
+            let mut chain = VecDeque::from([*line]);
+            while let Some(next) = take_from(chain.back().unwrap().b, &mut used) {
+                chain.push_back(next);
+            }
+            while let Some(previous) = take_from(chain.front().unwrap().a, &mut used) {
+                chain.push_front(previous.reversed());
+            }
+            chains.push(LineGroup::new(chain.into()));
+        }
+        chains
+    }
+
+    /// Same lines reordered so touching ones follow each other.
+    pub fn joined(&self) -> LineGroup {
+        let mut res = LineGroup::default();
+        for chain in self.chains() {
+            res.extend(chain);
+        }
+        res
+    }
+
+    /// Drops zero-length lines and lines drawn more than once, whatever their direction.
+    pub fn cleaned(&self) -> LineGroup {
+        let mut seen = HashSet::new();
+        LineGroup::new(
+            self.get_lines()
+                .iter()
+                .filter(|line| {
+                    let (a, b) = (point_key(line.a), point_key(line.b));
+                    a != b && seen.insert((a.min(b), a.max(b)))
+                })
+                .copied()
+                .collect(),
+        )
+    }
+
+    /// Removes the points where a chain keeps going straight, within `tolerance`. Every removed
+    /// point stays within `tolerance` of the line that replaces it.
+    pub fn merged_collinear(&self, tolerance: f32) -> LineGroup {
+        self.map_runs(|points| {
+            let last = points.len() - 1;
+            let mut kept = vec![0];
+            for i in 1..last {
+                let start = *kept.last().unwrap();
+                let chord = Line::new(points[start], points[i + 1]);
+                let goes_on = (points[i] - points[start]).dot(points[i + 1] - points[i]) >= 0.;
+                let fits = (start + 1..=i)
+                    .all(|j| chord.closest_point(points[j]).distance(points[j]) <= tolerance);
+                if !goes_on || !fits {
+                    kept.push(i);
+                }
+            }
+            kept.push(last);
+            kept.into_iter().map(|i| points[i]).collect()
+        })
+    }
+
+    /// Douglas-Peucker simplification of every chain, no point moves further than `tolerance`.
+    pub fn simplified(&self, tolerance: f32) -> LineGroup {
+        self.map_runs(|points| {
+            let mut keep = vec![false; points.len()];
+            keep[0] = true;
+            keep[points.len() - 1] = true;
+            let mut ranges = vec![(0, points.len() - 1)];
+            while let Some((start, end)) = ranges.pop() {
+                let segment = Line::new(points[start], points[end]);
+                let farthest = (start + 1..end)
+                    .map(|i| (i, segment.closest_point(points[i]).distance(points[i])))
+                    .max_by(|a, b| a.1.total_cmp(&b.1));
+                if let Some((index, distance)) = farthest {
+                    if distance > tolerance {
+                        keep[index] = true;
+                        ranges.push((start, index));
+                        ranges.push((index, end));
+                    }
+                }
+            }
+            points
+                .iter()
+                .zip(keep)
+                .filter_map(|(point, keep)| keep.then_some(*point))
+                .collect()
+        })
+    }
+
+    /// Cleans and chains the group, then rebuilds every run of same looking lines from the points
+    /// returned by `f`.
+    fn map_runs(&self, f: impl Fn(&[Vec2]) -> Vec<Vec2>) -> LineGroup {
+        let mut res = LineGroup::default();
+        for chain in self.cleaned().chains() {
+            for run in chain.get_lines().chunk_by(|a, b| a.looks_like(b)) {
+                let mut points = vec![run[0].a];
+                points.extend(run.iter().map(|l| l.b));
+                let points = f(&points);
+                for (a, b) in points.iter().zip(points.iter().skip(1)) {
+                    res.add_line(Line {
+                        a: *a,
+                        b: *b,
+                        ..run[0]
+                    });
+                }
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Distance from every point of `original` to the closest line of `simplified`.
+    fn max_deviation(original: &LineGroup, simplified: &LineGroup) -> f32 {
+        original
+            .get_lines()
+            .iter()
+            .flat_map(|l| [l.a, l.b])
+            .map(|p| p.distance(simplified.closest_point(p).unwrap()))
+            .fold(0., f32::max)
+    }
+
+    #[test]
+    fn merged_collinear_stays_within_tolerance_on_curves() {
+        let circle = LineGroup::generate_circle(100., 360);
+        let merged = circle.merged_collinear(0.5);
+        assert!(merged.get_lines().len() < circle.get_lines().len());
+        assert!(max_deviation(&circle, &merged) <= 0.5 + 1e-3);
+    }
+
+    #[test]
+    fn merged_collinear_drops_straight_points() {
+        let line = LineGroup::generate_continuous(vec![
+            Vec2::new(0., 0.),
+            Vec2::new(5., 0.),
+            Vec2::new(10., 0.),
+            Vec2::new(10., 10.),
+        ]);
+        let merged = line.merged_collinear(0.01);
+        assert_eq!(merged.get_lines().len(), 2);
+    }
+
+    fn assert_head_to_tail(chain: &LineGroup) {
+        for pair in chain.get_lines().windows(2) {
+            assert_eq!(pair[0].b, pair[1].a);
+        }
+    }
+
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::new(0., 0.),
+            Vec2::new(10., 0.),
+            Vec2::new(10., 10.),
+            Vec2::new(0., 10.),
+        ]
+    }
+
+    #[test]
+    fn chains_follow_an_open_chain() {
+        let points = square();
+        let group = LineGroup::new(vec![
+            Line::new(points[1], points[2]),
+            Line::new(points[2], points[3]),
+            Line::new(points[0], points[1]),
+        ]);
+        let chains = group.chains();
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0], LineGroup::generate_continuous(points));
+    }
+
+    #[test]
+    fn chains_close_loops() {
+        let chains = LineGroup::generate_continuous_closed(square()).chains();
+        assert_eq!(chains.len(), 1);
+        let lines = chains[0].get_lines();
+        assert_eq!(lines.len(), 4);
+        assert_head_to_tail(&chains[0]);
+        assert_eq!(lines[3].b, lines[0].a);
+    }
+
+    #[test]
+    fn chains_turn_reversed_lines_around() {
+        let points = square();
+        let group = LineGroup::new(vec![
+            Line::new(points[0], points[1]),
+            Line::new(points[2], points[1]),
+            Line::new(points[2], points[3]).with_style(LineStyle::Tapered { start: 1., end: 0. }),
+            Line::new(Vec2::new(20., 0.), Vec2::new(30., 0.)),
+        ]);
+        let chains = group.chains();
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0].get_lines().len(), 3);
+        assert_head_to_tail(&chains[0]);
+        assert_eq!(chains[0].get_lines()[1].a, points[1]);
+        assert_eq!(chains[1].get_lines().len(), 1);
+    }
+
+    #[test]
+    fn cleaned_drops_points_and_duplicates() {
+        let points = square();
+        let group = LineGroup::new(vec![
+            Line::new(points[0], points[1]),
+            Line::new(points[2], points[2]),
+            Line::new(points[1], points[0]),
+            Line::new(points[1], points[2]),
+            Line::new(points[1], points[2]),
+            Line::new(Vec2::new(-0., 0.), Vec2::new(0., -0.)),
+        ]);
+        assert_eq!(
+            group.cleaned(),
+            LineGroup::new(vec![
+                Line::new(points[0], points[1]),
+                Line::new(points[1], points[2]),
+            ])
+        );
+    }
+
+    #[test]
+    fn simplified_keeps_the_farthest_point() {
+        let bump = LineGroup::generate_continuous(vec![
+            Vec2::new(0., 0.),
+            Vec2::new(5., 1.),
+            Vec2::new(10., 3.),
+            Vec2::new(15., 1.),
+            Vec2::new(20., 0.),
+        ]);
+        assert_eq!(
+            bump.simplified(2.).to_unique_points(),
+            vec![Vec2::new(0., 0.), Vec2::new(10., 3.), Vec2::new(20., 0.)]
+        );
+        assert_eq!(bump.simplified(4.).get_lines().len(), 1);
+        assert_eq!(bump.simplified(0.1), bump);
+    }
+
+    #[test]
+    fn simplified_keeps_loops_closed() {
+        let circle = LineGroup::generate_circle(100., 360);
+        let simplified = circle.simplified(0.5);
+        let lines = simplified.get_lines();
+        assert!(lines.len() > 4 && lines.len() < circle.get_lines().len());
+        assert_head_to_tail(&simplified);
+        assert_eq!(lines.last().unwrap().b, lines[0].a);
+        assert!(max_deviation(&circle, &simplified) <= 0.5 + 1e-3);
+    }
+}
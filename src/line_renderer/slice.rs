@@ -0,0 +1,332 @@
+use bevy::{math::bounding::BoundingVolume, prelude::*};
+
+use super::{line::Line, line_group::LineGroup};
+
+const EPSILON: f32 = 1e-3;
+
+/// A point where the cut crosses the polygon boundary.
+#[derive(Clone, Copy, Debug)]
+struct Crossing {
+    point: Vec2,
+    /// Position along the cut, the integer part is the index of the cut segment.
+    cut_param: f32,
+}
+
+/// Part of the cut going through the inside of the polygon, from boundary to boundary.
+struct Chord {
+    points: Vec<Vec2>,
+}
+
+impl LineGroup {
+    /// Ordered vertices of the group when its lines form exactly one closed loop.
+    pub fn to_polygon(&self) -> Option<Vec<Vec2>> {
+        let lines = self.get_lines();
+        let first = lines.first()?;
+        let mut used = vec![false; lines.len()];
+        used[0] = true;
+        let mut points = vec![first.a];
+        let mut current = first.b;
+        while current.distance(points[0]) > EPSILON {
+            let (index, next) = lines.iter().enumerate().find_map(|(i, l)| {
+                if used[i] {
+                    None
+                } else if l.a.distance(current) < EPSILON {
+                    Some((i, l.b))
+                } else if l.b.distance(current) < EPSILON {
+                    Some((i, l.a))
+                } else {
+                    None
+                }
+            })?;
+            used[index] = true;
+            points.push(current);
+            current = next;
+        }
+        if used.contains(&false) || points.len() < 3 {
+            return None;
+        }
+        Some(points)
+    }
+
+    /// Cuts the polygon along an infinite line.
+    pub fn slice_by_line(&self, point: Vec2, direction: Dir2) -> Vec<LineGroup> {
+        if self.get_lines().is_empty() {
+            return vec![self.clone()];
+        }
+        let aabb = self.calculate_bounding_box();
+        let reach = aabb.half_size().length() + point.distance(aabb.center()) + 1.;
+        self.slice(&[point - *direction * reach, point + *direction * reach])
+    }
+
+    /// Cuts a closed polygon along a polyline, every stretch of the cut going through the
+    /// polygon splits off a new closed piece. Groups that aren't a single closed loop and cuts
+    /// that never cross the polygon leave the group whole.
+    pub fn slice(&self, cut: &[Vec2]) -> Vec<LineGroup> {
+        let Some(polygon) = self.to_polygon() else {
+            return vec![self.clone()];
+        };
+        let chords = find_chords(&polygon, cut);
+        if chords.is_empty() {
+            return vec![self.clone()];
+        }
+        let mut pieces = vec![polygon];
+        for chord in chords {
+            let Some(index) = pieces
+                .iter()
+                .position(|piece| polygon_contains(piece, chord_midpoint(&chord)))
+            else {
+                continue;
+            };
+            let piece = pieces.swap_remove(index);
+            match split_polygon(&piece, &chord) {
+                Some((first, second)) => pieces.extend([first, second]),
+                None => pieces.push(piece),
+            }
+        }
+        pieces
+            .into_iter()
+            .map(|piece| self.rebuilt_piece(&piece))
+            .collect()
+    }
+
+    /// Closes the piece with edges looking like the closest line of the group, which is the line
+    /// they came from for boundary edges.
+    fn rebuilt_piece(&self, piece: &[Vec2]) -> LineGroup {
+        let lines = self.get_lines();
+        LineGroup::new(
+            piece
+                .iter()
+                .zip(piece.iter().cycle().skip(1))
+                .map(|(a, b)| {
+                    let middle = (*a + *b) / 2.;
+                    let source = lines
+                        .iter()
+                        .min_by(|l, other| {
+                            l.closest_point(middle)
+                                .distance_squared(middle)
+                                .total_cmp(&other.closest_point(middle).distance_squared(middle))
+                        })
+                        .unwrap();
+                    Line {
+                        a: *a,
+                        b: *b,
+                        ..*source
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+fn find_chords(polygon: &[Vec2], cut: &[Vec2]) -> Vec<Chord> {
+    let mut crossings: Vec<Crossing> = Vec::new();
+    for (k, (c0, c1)) in cut.iter().zip(cut.iter().skip(1)).enumerate() {
+        let cut_line = Line::new(*c0, *c1);
+        for i in 0..polygon.len() {
+            let edge = Line::new(polygon[i], polygon[(i + 1) % polygon.len()]);
+            if let Some((t, _)) = cut_line.intersection_params(&edge) {
+                crossings.push(Crossing {
+                    point: c0.lerp(*c1, t),
+                    cut_param: k as f32 + t,
+                });
+            }
+        }
+    }
+    crossings.sort_by(|a, b| a.cut_param.total_cmp(&b.cut_param));
+    // Cuts through a vertex hit both of its edges.
+    crossings.dedup_by(|a, b| a.point.distance(b.point) < EPSILON);
+
+    let mut chords = Vec::new();
+    for (start, end) in crossings.iter().zip(crossings.iter().skip(1)) {
+        let middle = point_on_cut(cut, (start.cut_param + end.cut_param) / 2.);
+        if !polygon_contains(polygon, middle) {
+            continue;
+        }
+        let mut points = vec![start.point];
+        let first_vertex = start.cut_param.floor() as usize + 1;
+        let last_vertex = end.cut_param.ceil() as usize;
+        for vertex in cut.iter().take(last_vertex).skip(first_vertex) {
+            points.push(*vertex);
+        }
+        points.push(end.point);
+        points.dedup_by(|a, b| a.distance(*b) < EPSILON);
+        if points.len() >= 2 {
+            chords.push(Chord { points });
+        }
+    }
+    chords
+}
+
+fn point_on_cut(cut: &[Vec2], cut_param: f32) -> Vec2 {
+    let index = (cut_param.floor() as usize).min(cut.len() - 2);
+    cut[index].lerp(cut[index + 1], cut_param - index as f32)
+}
+
+fn chord_midpoint(chord: &Chord) -> Vec2 {
+    let total: f32 = chord.points.windows(2).map(|w| w[0].distance(w[1])).sum();
+    let mut remaining = total / 2.;
+    for w in chord.points.windows(2) {
+        let length = w[0].distance(w[1]);
+        if remaining <= length && length > 0. {
+            return w[0].lerp(w[1], remaining / length);
+        }
+        remaining -= length;
+    }
+    chord.points[0]
+}
+
+fn polygon_contains(polygon: &[Vec2], point: Vec2) -> bool {
+    LineGroup::generate_continuous_closed(polygon.to_vec()).contains_point(point)
+}
+
+/// Finds the edge a point lies on, with its position along that edge.
+fn locate_on_boundary(polygon: &[Vec2], point: Vec2) -> Option<(usize, f32)> {
+    (0..polygon.len())
+        .map(|i| {
+            let edge = Line::new(polygon[i], polygon[(i + 1) % polygon.len()]);
+            let closest = edge.closest_point(point);
+            let t = if edge.length() > 0. {
+                closest.distance(edge.a) / edge.length()
+            } else {
+                0.
+            };
+            (i, t, closest.distance(point))
+        })
+        .filter(|(_, _, distance)| *distance < EPSILON)
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(i, t, _)| (i, t))
+}
+
+/// Walks the boundary forward from `from` to `to`, both given as (edge, position on edge).
+fn boundary_walk(polygon: &[Vec2], from: (usize, f32), to: (usize, f32)) -> Vec<Vec2> {
+    let n = polygon.len();
+    let mut points = Vec::new();
+    if from.0 == to.0 && to.1 >= from.1 {
+        return points;
+    }
+    let mut i = (from.0 + 1) % n;
+    loop {
+        points.push(polygon[i]);
+        if i == to.0 {
+            break;
+        }
+        i = (i + 1) % n;
+    }
+    points
+}
+
+fn split_polygon(polygon: &[Vec2], chord: &Chord) -> Option<(Vec<Vec2>, Vec<Vec2>)> {
+    let start = *chord.points.first()?;
+    let end = *chord.points.last()?;
+    let start_location = locate_on_boundary(polygon, start)?;
+    let end_location = locate_on_boundary(polygon, end)?;
+    let inner = &chord.points[1..chord.points.len() - 1];
+
+    let mut first = vec![start];
+    first.extend(boundary_walk(polygon, start_location, end_location));
+    first.push(end);
+    first.extend(inner.iter().rev());
+
+    let mut second = vec![end];
+    second.extend(boundary_walk(polygon, end_location, start_location));
+    second.push(start);
+    second.extend(inner.iter());
+
+    let first = clean_polygon(first);
+    let second = clean_polygon(second);
+    if first.len() < 3 || second.len() < 3 {
+        return None;
+    }
+    Some((first, second))
+}
+
+fn clean_polygon(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    points.dedup_by(|a, b| a.distance(*b) < EPSILON);
+    while points.len() > 1 && points[0].distance(points[points.len() - 1]) < EPSILON {
+        points.pop();
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::color::palettes::css::RED;
+
+    use super::*;
+    use crate::line_renderer::line::LineStyle;
+
+    fn square() -> LineGroup {
+        LineGroup::generate_continuous_closed(vec![
+            Vec2::new(0., 0.),
+            Vec2::new(10., 0.),
+            Vec2::new(10., 10.),
+            Vec2::new(0., 10.),
+        ])
+    }
+
+    #[test]
+    fn line_through_square_makes_two_pieces() {
+        let pieces = square().slice_by_line(Vec2::new(5., 5.), Dir2::Y);
+        assert_eq!(pieces.len(), 2);
+        let mut areas: Vec<f32> = pieces.iter().map(|piece| piece.area()).collect();
+        areas.sort_by(|a, b| a.total_cmp(b));
+        assert!((areas[0] - 50.).abs() < 1e-3);
+        assert!((areas.iter().sum::<f32>() - square().area()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn crack_keeps_the_total_area() {
+        let cut = [
+            Vec2::new(-1., 2.),
+            Vec2::new(4., 6.),
+            Vec2::new(7., 3.),
+            Vec2::new(11., 8.),
+        ];
+        let pieces = square().slice(&cut);
+        assert_eq!(pieces.len(), 2);
+        let total: f32 = pieces.iter().map(|piece| piece.area()).sum();
+        assert!((total - square().area()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn chords_only_cover_the_inside() {
+        let cut = [Vec2::new(-5., 5.), Vec2::new(15., 5.)];
+        let chords = find_chords(&square().to_polygon().unwrap(), &cut);
+        assert_eq!(chords.len(), 1);
+        assert_eq!(
+            chords[0].points,
+            vec![Vec2::new(0., 5.), Vec2::new(10., 5.)]
+        );
+    }
+
+    #[test]
+    fn missing_cut_leaves_the_group_whole() {
+        let square = square();
+        let pieces = square.slice_by_line(Vec2::new(20., 0.), Dir2::Y);
+        assert_eq!(pieces, vec![square.clone()]);
+        let pieces = square.slice(&[Vec2::new(2., 2.), Vec2::new(8., 8.)]);
+        assert_eq!(pieces, vec![square]);
+    }
+
+    #[test]
+    fn empty_group_is_left_alone() {
+        let pieces = LineGroup::default().slice_by_line(Vec2::ZERO, Dir2::X);
+        assert_eq!(pieces, vec![LineGroup::default()]);
+    }
+
+    #[test]
+    fn pieces_keep_the_look_of_their_lines() {
+        let style = LineStyle::Dashed { pattern_length: 2. };
+        let square = square()
+            .with_color(RED.into())
+            .with_intensity(3.)
+            .with_style(style);
+        for piece in square.slice_by_line(Vec2::new(5., 5.), Dir2::Y) {
+            for line in piece.get_lines() {
+                assert_eq!(line.color, RED.into());
+                assert_eq!(line.intensity, 3.);
+                assert_eq!(line.style, style);
+            }
+        }
+    }
+}
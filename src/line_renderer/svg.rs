@@ -0,0 +1,437 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+use thiserror::Error;
+
+use super::{line::Line, line_group::LineGroup};
+
+/// SVG coordinates go down the y axis, ours go up, so y is negated both ways.
+/// Transforms, arcs and styles other than `stroke` are not supported.
+impl LineGroup {
+    /// Reads every `path`, `polyline`, `polygon` and `line` element, curves are flattened with
+    /// `tolerance`, see `LineGroup::generate_cubic_bezier`.
+    pub fn from_svg(svg: &str, tolerance: f32) -> Result<LineGroup, SvgError> {
+        let mut res = LineGroup::default();
+        for element in elements(svg) {
+            let mut lines = match element.name {
+                "path" => parse_path(element.attribute("d")?, tolerance)?,
+                "polyline" => {
+                    LineGroup::generate_continuous(parse_points(element.attribute("points")?)?)
+                }
+                "polygon" => LineGroup::generate_continuous_closed(parse_points(
+                    element.attribute("points")?,
+                )?),
+                "line" => {
+                    let mut ends = [0.; 4];
+                    for (end, name) in ends.iter_mut().zip(["x1", "y1", "x2", "y2"]) {
+                        *end = parse_number(element.attribute(name)?)?;
+                    }
+                    LineGroup::from_line(Line::new(
+                        Vec2::new(ends[0], 0. - ends[1]),
+                        Vec2::new(ends[2], 0. - ends[3]),
+                    ))
+                }
+                _ => continue,
+            };
+            if let Some(color) = element
+                .attribute("stroke")
+                .ok()
+                .and_then(|stroke| Srgba::hex(stroke).ok())
+            {
+                lines = lines.with_color(color.into());
+            }
+            if let Ok(intensity) = element.attribute("data-intensity") {
+                lines = lines.with_intensity(parse_number(intensity)?);
+            }
+            res.extend(lines);
+        }
+        Ok(res)
+    }
+
+    /// One `line` element per line, framed by the bounding box of the group.
+    pub fn to_svg(&self) -> String {
+        // `0. - y` rather than `-y` keeps `-0` out of the file, and out of groups read back.
+        let view_box = if self.get_lines().is_empty() {
+            "0 0 0 0".to_owned()
+        } else {
+            let aabb = self.calculate_bounding_box();
+            format!(
+                "{} {} {} {}",
+                aabb.min.x,
+                0. - aabb.max.y,
+                aabb.max.x - aabb.min.x,
+                aabb.max.y - aabb.min.y
+            )
+        };
+        let mut svg =
+            format!("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{view_box}\">\n");
+        for line in self.get_lines() {
+            let _ = write!(
+                svg,
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\"",
+                line.a.x,
+                0. - line.a.y,
+                line.b.x,
+                0. - line.b.y,
+                line.color.to_srgba().to_hex()
+            );
+            if line.intensity != 1. {
+                let _ = write!(svg, " data-intensity=\"{}\"", line.intensity);
+            }
+            svg.push_str("/>\n");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SvgError {
+    #[error("`<{element}>` has no `{attribute}` attribute")]
+    MissingAttribute { element: String, attribute: String },
+    #[error("invalid number `{0}`")]
+    InvalidNumber(String),
+    #[error("path data starts with a number instead of a command")]
+    MissingCommand,
+    #[error("unsupported path command `{0}`")]
+    UnsupportedCommand(char),
+}
+
+struct Element<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Element<'a> {
+    fn attribute(&self, name: &str) -> Result<&'a str, SvgError> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| SvgError::MissingAttribute {
+                element: self.name.to_owned(),
+                attribute: name.to_owned(),
+            })
+    }
+}
+
+/// Every opening tag of the document, good enough for the flat files vector tools export.
+fn elements(svg: &str) -> Vec<Element<'_>> {
+    let mut elements = Vec::new();
+    let mut rest = svg;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let end = rest.find('>').unwrap_or(rest.len());
+        let tag = rest[..end].trim_end_matches('/');
+        rest = &rest[end..];
+        if tag.starts_with(['/', '!', '?']) {
+            continue;
+        }
+        let name_end = tag.find(|c: char| c.is_whitespace()).unwrap_or(tag.len());
+        elements.push(Element {
+            name: &tag[..name_end],
+            attributes: attributes(&tag[name_end..]),
+        });
+    }
+    elements
+}
+
+fn attributes(mut tag: &str) -> Vec<(&str, &str)> {
+    let mut attributes = Vec::new();
+    while let Some(equals) = tag.find('=') {
+        let name = tag[..equals].trim();
+        let value_part = tag[equals + 1..].trim_start();
+        let Some(quote) = value_part
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+        else {
+            break;
+        };
+        let Some(length) = value_part[1..].find(quote) else {
+            break;
+        };
+        attributes.push((name, &value_part[1..length + 1]));
+        tag = &value_part[length + 2..];
+    }
+    attributes
+}
+
+fn parse_number(text: &str) -> Result<f32, SvgError> {
+    text.trim()
+        .parse()
+        .map_err(|_| SvgError::InvalidNumber(text.to_owned()))
+}
+
+fn parse_points(text: &str) -> Result<Vec<Vec2>, SvgError> {
+    let numbers = numbers(text)?;
+    Ok(numbers
+        .chunks_exact(2)
+        .map(|pair| Vec2::new(pair[0], 0. - pair[1]))
+        .collect())
+}
+
+/// Splits a list like `10,-5.5.5e2` into its numbers, SVG allows leaving out most separators.
+fn numbers(text: &str) -> Result<Vec<f32>, SvgError> {
+    let mut numbers = Vec::new();
+    let mut current = String::new();
+    let mut previous = ' ';
+    for c in text.chars() {
+        let starts_new = match c {
+            '-' | '+' => !matches!(previous, 'e' | 'E'),
+            '.' => current.contains('.') && !current.contains(['e', 'E']),
+            ',' => true,
+            c if c.is_whitespace() => true,
+            _ => false,
+        };
+        if starts_new && !current.is_empty() {
+            numbers.push(parse_number(&current)?);
+            current.clear();
+        }
+        if c != ',' && !c.is_whitespace() {
+            current.push(c);
+        }
+        previous = c;
+    }
+    if !current.is_empty() {
+        numbers.push(parse_number(&current)?);
+    }
+    Ok(numbers)
+}
+
+fn parse_path(data: &str, tolerance: f32) -> Result<LineGroup, SvgError> {
+    let mut commands: Vec<(char, Vec<f32>)> = Vec::new();
+    let mut start = 0;
+    for (i, c) in data.char_indices() {
+        if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            if commands.is_empty() && !data[..i].trim().is_empty() {
+                return Err(SvgError::MissingCommand);
+            }
+            if let Some((_, arguments)) = commands.last_mut() {
+                *arguments = numbers(&data[start..i])?;
+            }
+            commands.push((c, Vec::new()));
+            start = i + c.len_utf8();
+        }
+    }
+    if let Some((_, arguments)) = commands.last_mut() {
+        *arguments = numbers(&data[start..])?;
+    }
+
+    // SVG's y axis is flipped at the end, so the curves are built in SVG space.
+    let mut res = LineGroup::default();
+    let mut current = Vec2::ZERO;
+    let mut subpath_start = Vec2::ZERO;
+    // Last control point of the previous command, for the smooth curve commands to mirror.
+    let mut last_cubic: Option<Vec2> = None;
+    let mut last_quadratic: Option<Vec2> = None;
+    for (command, arguments) in commands {
+        let relative = command.is_ascii_lowercase();
+        let offset = |p: Vec2, current: Vec2| if relative { current + p } else { p };
+        let points: Vec<Vec2> = arguments
+            .chunks_exact(2)
+            .map(|pair| Vec2::new(pair[0], pair[1]))
+            .collect();
+        let mut next_cubic = None;
+        let mut next_quadratic = None;
+        match command.to_ascii_uppercase() {
+            'M' => {
+                for (i, point) in points.iter().enumerate() {
+                    let point = offset(*point, current);
+                    if i == 0 {
+                        subpath_start = point;
+                    } else {
+                        res.add_line(Line::new(current, point));
+                    }
+                    current = point;
+                }
+            }
+            'L' => {
+                for point in points {
+                    let point = offset(point, current);
+                    res.add_line(Line::new(current, point));
+                    current = point;
+                }
+            }
+            'H' | 'V' => {
+                for value in arguments {
+                    let point = match (command.to_ascii_uppercase(), relative) {
+                        ('H', true) => Vec2::new(current.x + value, current.y),
+                        ('H', false) => Vec2::new(value, current.y),
+                        (_, true) => Vec2::new(current.x, current.y + value),
+                        (_, false) => Vec2::new(current.x, value),
+                    };
+                    res.add_line(Line::new(current, point));
+                    current = point;
+                }
+            }
+            'Z' => {
+                if current != subpath_start {
+                    res.add_line(Line::new(current, subpath_start));
+                }
+                current = subpath_start;
+            }
+            'C' => {
+                for controls in points.chunks_exact(3) {
+                    let (c1, c2, end) = (
+                        offset(controls[0], current),
+                        offset(controls[1], current),
+                        offset(controls[2], current),
+                    );
+                    res.extend(LineGroup::generate_cubic_bezier(
+                        current, c1, c2, end, tolerance,
+                    ));
+                    next_cubic = Some(c2);
+                    current = end;
+                }
+            }
+            'S' => {
+                for controls in points.chunks_exact(2) {
+                    let c1 = reflect(next_cubic.or(last_cubic), current);
+                    let (c2, end) = (offset(controls[0], current), offset(controls[1], current));
+                    res.extend(LineGroup::generate_cubic_bezier(
+                        current, c1, c2, end, tolerance,
+                    ));
+                    next_cubic = Some(c2);
+                    current = end;
+                }
+            }
+            'Q' => {
+                for controls in points.chunks_exact(2) {
+                    let (control, end) =
+                        (offset(controls[0], current), offset(controls[1], current));
+                    res.extend(LineGroup::generate_quadratic_bezier(
+                        current, control, end, tolerance,
+                    ));
+                    next_quadratic = Some(control);
+                    current = end;
+                }
+            }
+            'T' => {
+                for end in points {
+                    let control = reflect(next_quadratic.or(last_quadratic), current);
+                    let end = offset(end, current);
+                    res.extend(LineGroup::generate_quadratic_bezier(
+                        current, control, end, tolerance,
+                    ));
+                    next_quadratic = Some(control);
+                    current = end;
+                }
+            }
+            other => return Err(SvgError::UnsupportedCommand(other)),
+        }
+        last_cubic = next_cubic;
+        last_quadratic = next_quadratic;
+    }
+    Ok(res.flipped_vertically())
+}
+
+/// Control point mirrored around `current`, or `current` itself without a previous curve.
+fn reflect(control: Option<Vec2>, current: Vec2) -> Vec2 {
+    control.map_or(current, |control| 2. * current - control)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::color::palettes::css::RED;
+
+    use super::*;
+
+    fn assert_lines(group: &LineGroup, expected: &[(Vec2, Vec2)]) {
+        let lines = group.get_lines();
+        assert_eq!(lines.len(), expected.len(), "{group:?}");
+        for (line, (a, b)) in lines.iter().zip(expected) {
+            assert!(
+                line.a.abs_diff_eq(*a, 1e-5) && line.b.abs_diff_eq(*b, 1e-5),
+                "{line:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn path_lines() {
+        let svg = r#"<svg><path d="M0,0 L10,0 V-10 H0 Z"/></svg>"#;
+        let group = LineGroup::from_svg(svg, 0.1).unwrap();
+        assert_lines(
+            &group,
+            &[
+                (Vec2::new(0., 0.), Vec2::new(10., 0.)),
+                (Vec2::new(10., 0.), Vec2::new(10., 10.)),
+                (Vec2::new(10., 10.), Vec2::new(0., 10.)),
+                (Vec2::new(0., 10.), Vec2::new(0., 0.)),
+            ],
+        );
+    }
+
+    #[test]
+    fn relative_path_lines() {
+        let svg = r#"<svg><path d="m1 1 l2 0 v2 h-2 z"/></svg>"#;
+        let group = LineGroup::from_svg(svg, 0.1).unwrap();
+        assert_lines(
+            &group,
+            &[
+                (Vec2::new(1., -1.), Vec2::new(3., -1.)),
+                (Vec2::new(3., -1.), Vec2::new(3., -3.)),
+                (Vec2::new(3., -3.), Vec2::new(1., -3.)),
+                (Vec2::new(1., -3.), Vec2::new(1., -1.)),
+            ],
+        );
+    }
+
+    #[test]
+    fn polyline_polygon_and_line() {
+        let svg = r##"<svg>
+            <polyline points="0,0 10,0 10,-10"/>
+            <polygon points="20 0 30 0 30 -10"/>
+            <line x1="0" y1="-20" x2="5" y2="-25" stroke="#ff0000"/>
+        </svg>"##;
+        let group = LineGroup::from_svg(svg, 0.1).unwrap();
+        assert_lines(
+            &group,
+            &[
+                (Vec2::new(0., 0.), Vec2::new(10., 0.)),
+                (Vec2::new(10., 0.), Vec2::new(10., 10.)),
+                (Vec2::new(20., 0.), Vec2::new(30., 0.)),
+                (Vec2::new(30., 0.), Vec2::new(30., 10.)),
+                (Vec2::new(30., 10.), Vec2::new(20., 0.)),
+                (Vec2::new(0., 20.), Vec2::new(5., 25.)),
+            ],
+        );
+        assert_eq!(group.get_lines()[5].color, RED.into());
+    }
+
+    #[test]
+    fn unsupported_command_is_an_error() {
+        let svg = r#"<svg><path d="M0,0 A5,5 0 0 1 10,0"/></svg>"#;
+        assert!(matches!(
+            LineGroup::from_svg(svg, 0.1),
+            Err(SvgError::UnsupportedCommand('A'))
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let group = LineGroup::generate_continuous_closed(vec![
+            Vec2::new(0., 0.),
+            Vec2::new(10.5, 0.),
+            Vec2::new(10.5, -7.25),
+        ])
+        .concat(
+            LineGroup::from_line(Line::new(Vec2::new(-3., 2.), Vec2::new(4., 8.)))
+                .with_color(RED.into())
+                .with_intensity(2.5),
+        );
+        let svg = group.to_svg();
+        assert_eq!(LineGroup::from_svg(&svg, 0.1).unwrap(), group);
+    }
+
+    #[test]
+    fn empty_round_trip() {
+        let svg = LineGroup::default().to_svg();
+        assert!(svg.contains(r#"viewBox="0 0 0 0""#));
+        assert_eq!(
+            LineGroup::from_svg(&svg, 0.1).unwrap(),
+            LineGroup::default()
+        );
+    }
+}
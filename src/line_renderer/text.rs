@@ -0,0 +1,213 @@
+use bevy::{math::bounding::Aabb2d, prelude::*};
+
+use super::{
+    font::{Glyph, ASCENT, DESCENT, LINE_HEIGHT},
+    line_group::LineGroup,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Lays out text with the line font. Lines are aligned around x = 0 and the first line's
+/// glyphs are centered on y = 0, following lines go downwards.
+#[derive(Clone, Debug)]
+pub struct TextLayout {
+    text: String,
+    align: TextAlign,
+    max_width: Option<f32>,
+    line_spacing: f32,
+}
+
+impl TextLayout {
+    pub fn new(text: impl Into<String>) -> TextLayout {
+        TextLayout {
+            text: text.into(),
+            align: TextAlign::Left,
+            max_width: None,
+            line_spacing: 1.,
+        }
+    }
+
+    pub fn with_align(&self, align: TextAlign) -> TextLayout {
+        let mut new = self.clone();
+        new.align = align;
+        new
+    }
+
+    pub fn with_max_width(&self, max_width: f32) -> TextLayout {
+        let mut new = self.clone();
+        new.max_width = Some(max_width);
+        new
+    }
+
+    /// Multiplier applied to the font's line height.
+    pub fn with_line_spacing(&self, line_spacing: f32) -> TextLayout {
+        let mut new = self.clone();
+        new.line_spacing = line_spacing;
+        new
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for paragraph in self.text.split('\n') {
+            match self.max_width {
+                Some(max_width) => lines.extend(wrap(paragraph, max_width)),
+                None => lines.push(paragraph.to_owned()),
+            }
+        }
+        lines
+    }
+
+    pub fn measure(&self) -> Aabb2d {
+        let lines = self.lines();
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        for line in lines.iter() {
+            let width = text_width(line);
+            let start = self.line_start(width);
+            min_x = min_x.min(start);
+            max_x = max_x.max(start + width);
+        }
+        let bottom = -((lines.len() - 1) as f32) * self.line_height() + DESCENT;
+        Aabb2d {
+            min: Vec2::new(min_x, bottom),
+            max: Vec2::new(max_x, ASCENT),
+        }
+    }
+
+    pub fn build(&self) -> LineGroup {
+        let mut res = LineGroup::default();
+        for (i, line) in self.lines().iter().enumerate() {
+            let y = -(i as f32) * self.line_height();
+            let mut pen = self.line_start(text_width(line));
+            for c in line.chars() {
+                let glyph = Glyph::for_char(c);
+                res.extend(glyph.lines.offset(Vec2::new(pen + glyph.advance / 2., y)));
+                pen += glyph.advance;
+            }
+        }
+        res
+    }
+
+    fn line_height(&self) -> f32 {
+        LINE_HEIGHT * self.line_spacing
+    }
+
+    fn line_start(&self, width: f32) -> f32 {
+        match self.align {
+            TextAlign::Left => 0.,
+            TextAlign::Center => -width / 2.,
+            TextAlign::Right => -width,
+        }
+    }
+}
+
+pub fn text_width(text: &str) -> f32 {
+    text.chars().map(|c| Glyph::for_char(c).advance).sum()
+}
+
+fn wrap(paragraph: &str, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in paragraph.split(' ') {
+        if !current.is_empty() {
+            let candidate = format!("{current} {word}");
+            if text_width(&candidate) <= max_width {
+                current = candidate;
+                continue;
+            }
+            lines.push(std::mem::take(&mut current));
+        }
+        // Words longer than a whole line get broken wherever they overflow.
+        for c in word.chars() {
+            if !current.is_empty() && text_width(&current) + Glyph::for_char(c).advance > max_width
+            {
+                lines.push(std::mem::take(&mut current));
+            }
+            current.push(c);
+        }
+    }
+    lines.push(current);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_on_word_boundaries() {
+        let max_width = text_width("AB CD");
+        let layout = TextLayout::new("AB CD EF").with_max_width(max_width);
+        assert_eq!(layout.lines(), vec!["AB CD", "EF"]);
+        let layout = TextLayout::new("AB CD EF").with_max_width(max_width - 1.);
+        assert_eq!(layout.lines(), vec!["AB", "CD", "EF"]);
+    }
+
+    #[test]
+    fn breaks_words_longer_than_a_line() {
+        let layout = TextLayout::new("ABCDE").with_max_width(text_width("AB"));
+        assert_eq!(layout.lines(), vec!["AB", "CD", "E"]);
+    }
+
+    #[test]
+    fn keeps_explicit_line_breaks() {
+        assert_eq!(TextLayout::new("AB\nC").lines(), vec!["AB", "C"]);
+    }
+
+    #[test]
+    fn alignment_shifts_each_line_by_its_width() {
+        let (first, second) = ("AB", "C");
+        let text = format!("{first}\n{second}");
+        for (align, factor) in [(TextAlign::Center, 0.5), (TextAlign::Right, 1.)] {
+            let expected = TextLayout::new(first)
+                .build()
+                .offset(Vec2::new(-text_width(first) * factor, 0.))
+                .concat(
+                    TextLayout::new(second)
+                        .build()
+                        .offset(Vec2::new(-text_width(second) * factor, -LINE_HEIGHT)),
+                );
+            assert_eq!(TextLayout::new(&text).with_align(align).build(), expected);
+        }
+    }
+
+    #[test]
+    fn measure_follows_alignment() {
+        let width = text_width("AB");
+        let left = TextLayout::new("AB").measure();
+        assert_eq!((left.min.x, left.max.x), (0., width));
+        let center = TextLayout::new("AB")
+            .with_align(TextAlign::Center)
+            .measure();
+        assert_eq!((center.min.x, center.max.x), (-width / 2., width / 2.));
+        let right = TextLayout::new("AB").with_align(TextAlign::Right).measure();
+        assert_eq!((right.min.x, right.max.x), (-width, 0.));
+    }
+
+    #[test]
+    fn measure_matches_the_built_lines() {
+        for layout in [
+            TextLayout::new("Eg"),
+            TextLayout::new("Eg\nEg").with_align(TextAlign::Center),
+            TextLayout::new("Eg\nEg").with_line_spacing(1.5),
+        ] {
+            let measured = layout.measure();
+            let built = layout.build().calculate_bounding_box();
+            assert_eq!(built.max.y, measured.max.y);
+            assert_eq!(built.min.y, measured.min.y);
+            assert!(built.min.x >= measured.min.x && built.max.x <= measured.max.x);
+        }
+    }
+
+    #[test]
+    fn line_spacing_moves_following_lines() {
+        let measured = TextLayout::new("A\nA\nA").with_line_spacing(2.).measure();
+        assert_eq!(measured.min.y, -4. * LINE_HEIGHT + DESCENT);
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::VecDeque;
+
+use bevy::{math::bounding::BoundingVolume, prelude::*};
+
+use super::{
+    line::{Line, LineStyle},
+    line_group::LineGroup,
+    line_mesh::{on_change, LineMesh},
+    LineWidth,
+};
+
+/// Behind the entities leaving the trails.
+const TRAIL_Z: f32 = -1.;
+
+pub struct LineTrailPlugin;
+
+impl Plugin for LineTrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_trails.before(on_change));
+    }
+}
+
+/// Fading polyline following the entity's past positions, drawn by a separate entity so it
+/// doesn't move or rotate with its owner.
+#[derive(Component, Clone, Debug)]
+pub struct LineTrail {
+    /// Newest first.
+    samples: VecDeque<Vec2>,
+    max_samples: usize,
+    sample_interval: f32,
+    last_sample_time: Option<f32>,
+    max_length: Option<f32>,
+    /// Width multiplier at the oldest end, 1 at the newest.
+    width_falloff: f32,
+    width: f32,
+    color: Color,
+    intensity: f32,
+    mesh: Option<Entity>,
+}
+
+/// Marks the entity drawing the trail of another one.
+#[derive(Component)]
+pub struct LineTrailMesh(pub Entity);
+
+impl LineTrail {
+    pub fn new(max_samples: usize, sample_interval: f32) -> LineTrail {
+        let max_samples = max_samples.max(2);
+        LineTrail {
+            // Room for the sample pushed before truncating.
+            samples: VecDeque::with_capacity(max_samples + 1),
+            max_samples,
+            sample_interval,
+            last_sample_time: None,
+            max_length: None,
+            width_falloff: 0.,
+            width: 1.,
+            color: Color::WHITE,
+            intensity: 1.,
+            mesh: None,
+        }
+    }
+
+    /// Cuts the oldest part of the trail once it gets longer than `max_length` world units.
+    pub fn with_max_length(&self, max_length: f32) -> LineTrail {
+        let mut new = self.clone();
+        new.max_length = Some(max_length);
+        new
+    }
+
+    pub fn with_width(&self, width: f32, width_falloff: f32) -> LineTrail {
+        let mut new = self.clone();
+        new.width = width;
+        new.width_falloff = width_falloff;
+        new
+    }
+
+    pub fn with_color(&self, color: Color) -> LineTrail {
+        let mut new = self.clone();
+        new.color = color;
+        new
+    }
+
+    pub fn with_intensity(&self, intensity: f32) -> LineTrail {
+        let mut new = self.clone();
+        new.intensity = intensity;
+        new
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &Vec2> {
+        self.samples.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.last_sample_time = None;
+    }
+
+    /// Records `position` if a full interval passed since the last sample, returns whether it did.
+    pub fn sample(&mut self, position: Vec2, time: f32) -> bool {
+        if self
+            .last_sample_time
+            .is_some_and(|last| time - last < self.sample_interval)
+        {
+            return false;
+        }
+        self.last_sample_time = Some(time);
+        self.samples.push_front(position);
+        self.samples.truncate(self.max_samples);
+        if let Some(max_length) = self.max_length {
+            self.trim_to_length(max_length);
+        }
+        true
+    }
+
+    fn trim_to_length(&mut self, max_length: f32) {
+        let mut travelled = 0.;
+        for i in 1..self.samples.len() {
+            let (newer, older) = (self.samples[i - 1], self.samples[i]);
+            let length = newer.distance(older);
+            if travelled + length > max_length {
+                self.samples[i] = newer.move_towards(older, max_length - travelled);
+                self.samples.truncate(i + 1);
+                return;
+            }
+            travelled += length;
+        }
+    }
+
+    /// Lines from the newest sample to the oldest, fading out and narrowing along the way.
+    pub fn to_line_group(&self) -> LineGroup {
+        let segments = self.samples.len().saturating_sub(1);
+        let mut res = LineGroup::default();
+        for (i, (newer, older)) in self
+            .samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .enumerate()
+        {
+            let start = i as f32 / segments as f32;
+            let end = (i + 1) as f32 / segments as f32;
+            res.add_line(
+                Line::new(*newer, *older)
+                    .with_color(self.color.with_alpha(self.color.alpha() * (1. - start)))
+                    .with_intensity(self.intensity)
+                    .with_style(LineStyle::Tapered {
+                        start: 1.0.lerp(self.width_falloff, start),
+                        end: 1.0.lerp(self.width_falloff, end),
+                    }),
+            );
+        }
+        res
+    }
+}
+
+fn update_trails(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut trail_q: Query<(Entity, &GlobalTransform, &mut LineTrail)>,
+    trail_mesh_q: Query<(Entity, &LineTrailMesh)>,
+) {
+    for (mesh, owner) in trail_mesh_q.iter() {
+        if !trail_q.contains(owner.0) {
+            commands.entity(mesh).despawn();
+        }
+    }
+    for (entity, transform, mut trail) in trail_q.iter_mut() {
+        // A single sample doesn't make a line yet.
+        if !trail.sample(transform.translation().xy(), time.elapsed_secs())
+            || trail.samples.len() < 2
+        {
+            continue;
+        }
+        let lines = trail.to_line_group();
+        let center = lines.calculate_bounding_box().center();
+        let bundle = (
+            LineMesh(lines.offset(-center)),
+            Transform::from_translation(center.extend(TRAIL_Z)),
+            LineWidth::World(trail.width),
+        );
+        match trail.mesh {
+            Some(mesh) => {
+                commands.entity(mesh).try_insert(bundle);
+            }
+            None => {
+                trail.mesh = Some(commands.spawn((bundle, LineTrailMesh(entity))).id());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(trail: &LineTrail) -> Vec<Vec2> {
+        trail.samples().copied().collect()
+    }
+
+    #[test]
+    fn samples_once_per_interval() {
+        let mut trail = LineTrail::new(10, 0.5);
+        assert!(trail.sample(Vec2::new(0., 0.), 1.));
+        assert!(!trail.sample(Vec2::new(1., 0.), 1.2));
+        assert!(!trail.sample(Vec2::new(2., 0.), 1.49));
+        assert!(trail.sample(Vec2::new(3., 0.), 1.5));
+        assert_eq!(samples(&trail), vec![Vec2::new(3., 0.), Vec2::new(0., 0.)]);
+        trail.clear();
+        assert!(trail.sample(Vec2::new(4., 0.), 1.6));
+    }
+
+    #[test]
+    fn keeps_the_newest_max_samples() {
+        let mut trail = LineTrail::new(3, 0.);
+        for i in 0..5 {
+            trail.sample(Vec2::new(i as f32, 0.), i as f32);
+        }
+        assert_eq!(
+            samples(&trail),
+            vec![Vec2::new(4., 0.), Vec2::new(3., 0.), Vec2::new(2., 0.)]
+        );
+        let mut short = LineTrail::new(0, 0.);
+        for i in 0..5 {
+            short.sample(Vec2::new(i as f32, 0.), i as f32);
+        }
+        assert_eq!(short.samples().count(), 2);
+    }
+
+    #[test]
+    fn max_length_cuts_the_oldest_segment() {
+        let mut trail = LineTrail::new(10, 0.).with_max_length(2.5);
+        for (i, position) in [(0., 0.), (1., 0.), (1., 1.), (1., 3.)]
+            .into_iter()
+            .enumerate()
+        {
+            trail.sample(Vec2::from(position), i as f32);
+        }
+        assert_eq!(
+            samples(&trail),
+            vec![Vec2::new(1., 3.), Vec2::new(1., 1.), Vec2::new(1., 0.5)]
+        );
+    }
+
+    #[test]
+    fn fades_and_narrows_towards_the_oldest_sample() {
+        let mut trail = LineTrail::new(10, 0.)
+            .with_color(Color::srgba(1., 0., 0., 0.8))
+            .with_width(2., 0.);
+        for i in 0..5 {
+            trail.sample(Vec2::new(i as f32, 0.), i as f32);
+        }
+        let group = trail.to_line_group();
+        let lines = group.get_lines();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].a, Vec2::new(4., 0.));
+        assert_eq!(lines[3].b, Vec2::new(0., 0.));
+        let alphas: Vec<f32> = lines.iter().map(|l| l.color.alpha()).collect();
+        assert_eq!(alphas, vec![0.8, 0.6, 0.4, 0.2]);
+        assert_eq!(
+            lines[0].style,
+            LineStyle::Tapered {
+                start: 1.,
+                end: 0.75
+            }
+        );
+        assert_eq!(
+            lines[3].style,
+            LineStyle::Tapered {
+                start: 0.25,
+                end: 0.
+            }
+        );
+        for pair in lines.windows(2) {
+            let (LineStyle::Tapered { end, .. }, LineStyle::Tapered { start, .. }) =
+                (pair[0].style, pair[1].style)
+            else {
+                panic!("trail lines are tapered");
+            };
+            assert_eq!(end, start);
+        }
+    }
+
+    #[test]
+    fn no_lines_below_two_samples() {
+        let mut trail = LineTrail::new(10, 0.);
+        assert!(trail.to_line_group().get_lines().is_empty());
+        trail.sample(Vec2::ZERO, 0.);
+        assert!(trail.to_line_group().get_lines().is_empty());
+        trail.sample(Vec2::X, 1.);
+        assert_eq!(trail.to_line_group().get_lines().len(), 1);
+    }
+}
@@ -13,6 +13,8 @@ use game::{ship::spawn_ship, CurrentGameState, GameState};
 use health::HealthPlugin;
 use level_manager::{LevelManager, LevelManagerPlugin, LevelReset};
 use line_renderer::*;
+use particles::ParticlePlugin;
+use rand::GameRng;
 
 pub mod audio_manager;
 pub mod bevy_utils;
@@ -24,6 +26,7 @@ mod game;
 pub mod health;
 mod level_manager;
 pub mod line_renderer;
+pub mod particles;
 mod rand;
 
 fn main() {
@@ -53,10 +56,12 @@ fn main() {
             GamePlugin,
             LevelManagerPlugin,
             CameraShakePlugin,
+            ParticlePlugin,
         ))
         .insert_resource(ClearColor(Color::srgb(0.05, 0.05, 0.05)))
         .insert_resource(Gravity(Vec2::ZERO))
         .insert_resource(AudioManagerResource::new(0.01))
+        .insert_resource(GameRng::from_args())
         .add_systems(Startup, setup)
         .add_systems(Update, handle_reset)
         .add_systems(Update, level_setup.run_if(on_event::<LevelReset>))
@@ -77,8 +82,10 @@ fn level_setup(
     mut commands: Commands,
     mut level_manager: LevelManager,
     mut game_state: ResMut<CurrentGameState>,
+    mut rng: ResMut<GameRng>,
 ) {
     game_state.0 = GameState::PLAYING;
+    rng.reset();
     let ship = spawn_ship(&mut commands);
     let camera = spawn_camera(&mut level_manager, ship);
     spawn_ui(&mut commands, camera);
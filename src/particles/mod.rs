@@ -0,0 +1,262 @@
+use std::ops::Range;
+
+use avian2d::prelude::{AngularVelocity, LinearDamping, LinearVelocity, Mass, RigidBody};
+use bevy::prelude::*;
+use rand::{distributions::uniform::SampleUniform, Rng};
+
+use crate::{
+    delayed_despawn::DelayedDespawn,
+    level_manager::LevelScoped,
+    line::Line,
+    line_group::LineGroup,
+    line_mesh::LineMesh,
+    rand::{random_range, GameRng, RngStream},
+    LineWidth,
+};
+
+/// What each particle of a burst looks like.
+#[derive(Clone, Debug)]
+pub enum ParticleShape {
+    /// A single line pointing along the particle's velocity.
+    Streak { length: Range<f32> },
+    /// Every line of the group flies off on its own, keeping its place in the burst's transform.
+    /// The count of the preset is ignored.
+    Shards(LineGroup),
+}
+
+/// Describes a burst of line particles, ranges are sampled once per particle.
+#[derive(Clone, Debug)]
+pub struct ParticlePreset {
+    pub shape: ParticleShape,
+    pub count: Range<u32>,
+    pub speed: Range<f32>,
+    /// Middle of the cone particles are thrown in, in degrees from the local up direction.
+    pub direction: f32,
+    /// Half angle of the cone, in degrees.
+    pub spread: f32,
+    pub damping: f32,
+    pub angular_speed: Range<f32>,
+    /// Seconds before particles despawn, they stay until the level resets without one.
+    pub lifetime: Option<Range<f32>>,
+    /// Share of the burst's velocity added to every particle.
+    pub inherit_velocity: f32,
+    pub width: Option<LineWidth>,
+}
+
+impl ParticlePreset {
+    pub fn streaks(length: Range<f32>) -> ParticlePreset {
+        ParticlePreset::new(ParticleShape::Streak { length })
+    }
+
+    pub fn shards(lines: LineGroup) -> ParticlePreset {
+        ParticlePreset::new(ParticleShape::Shards(lines))
+    }
+
+    fn new(shape: ParticleShape) -> ParticlePreset {
+        ParticlePreset {
+            shape,
+            count: 1..2,
+            speed: 0.0..0.,
+            direction: 0.,
+            spread: 180.,
+            damping: 0.,
+            angular_speed: 0.0..0.,
+            lifetime: None,
+            inherit_velocity: 0.,
+            width: None,
+        }
+    }
+
+    pub fn with_count(&self, count: Range<u32>) -> ParticlePreset {
+        let mut new = self.clone();
+        new.count = count;
+        new
+    }
+
+    pub fn with_speed(&self, speed: Range<f32>) -> ParticlePreset {
+        let mut new = self.clone();
+        new.speed = speed;
+        new
+    }
+
+    pub fn with_cone(&self, direction: f32, spread: f32) -> ParticlePreset {
+        let mut new = self.clone();
+        new.direction = direction;
+        new.spread = spread;
+        new
+    }
+
+    pub fn with_damping(&self, damping: f32) -> ParticlePreset {
+        let mut new = self.clone();
+        new.damping = damping;
+        new
+    }
+
+    pub fn with_angular_speed(&self, angular_speed: Range<f32>) -> ParticlePreset {
+        let mut new = self.clone();
+        new.angular_speed = angular_speed;
+        new
+    }
+
+    pub fn with_lifetime(&self, lifetime: Range<f32>) -> ParticlePreset {
+        let mut new = self.clone();
+        new.lifetime = Some(lifetime);
+        new
+    }
+
+    pub fn with_inherit_velocity(&self, inherit_velocity: f32) -> ParticlePreset {
+        let mut new = self.clone();
+        new.inherit_velocity = inherit_velocity;
+        new
+    }
+
+    pub fn with_width(&self, width: LineWidth) -> ParticlePreset {
+        let mut new = self.clone();
+        new.width = Some(width);
+        new
+    }
+}
+
+/// Spawns the particles of `preset` at `transform` once.
+#[derive(Event, Clone, Debug)]
+pub struct SpawnBurst {
+    pub preset: ParticlePreset,
+    pub transform: Transform,
+    pub velocity: Vec2,
+}
+
+impl SpawnBurst {
+    pub fn new(preset: ParticlePreset, transform: Transform) -> SpawnBurst {
+        SpawnBurst {
+            preset,
+            transform,
+            velocity: Vec2::ZERO,
+        }
+    }
+
+    pub fn with_velocity(mut self, velocity: Vec2) -> SpawnBurst {
+        self.velocity = velocity;
+        self
+    }
+}
+
+/// Keeps sending bursts of `preset` from the entity while enabled.
+#[derive(Component, Clone, Debug)]
+pub struct LineParticleEmitter {
+    pub preset: ParticlePreset,
+    /// Bursts per second.
+    pub rate: f32,
+    pub enabled: bool,
+    since_last_burst: f32,
+}
+
+impl LineParticleEmitter {
+    pub fn new(preset: ParticlePreset, rate: f32) -> LineParticleEmitter {
+        LineParticleEmitter {
+            preset,
+            rate,
+            enabled: true,
+            since_last_burst: 0.,
+        }
+    }
+}
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SpawnBurst>()
+            .add_systems(Update, (handle_emitters, spawn_bursts).chain());
+    }
+}
+
+fn handle_emitters(
+    time: Res<Time>,
+    mut burst_e: EventWriter<SpawnBurst>,
+    mut emitter_q: Query<(
+        &GlobalTransform,
+        Option<&LinearVelocity>,
+        &mut LineParticleEmitter,
+    )>,
+) {
+    for (transform, velocity, mut emitter) in emitter_q.iter_mut() {
+        if !emitter.enabled || emitter.rate <= 0. {
+            emitter.since_last_burst = 0.;
+            continue;
+        }
+        emitter.since_last_burst += time.delta_secs();
+        while emitter.since_last_burst >= 1. / emitter.rate {
+            emitter.since_last_burst -= 1. / emitter.rate;
+            burst_e.send(
+                SpawnBurst::new(emitter.preset.clone(), transform.compute_transform())
+                    .with_velocity(velocity.map_or(Vec2::ZERO, |velocity| velocity.0)),
+            );
+        }
+    }
+}
+
+fn spawn_bursts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut burst_e: EventReader<SpawnBurst>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = game_rng.stream(RngStream::Visuals);
+    for burst in burst_e.read() {
+        let preset = &burst.preset;
+        let particles: Vec<LineGroup> = match &preset.shape {
+            ParticleShape::Streak { length } => (0..sample(rng, &preset.count))
+                .map(|_| {
+                    LineGroup::from_line(Line::new(Vec2::ZERO, Vec2::new(0., sample(rng, length))))
+                })
+                .collect(),
+            ParticleShape::Shards(lines) => lines
+                .get_lines()
+                .iter()
+                .map(|line| LineGroup::from_line(*line))
+                .collect(),
+        };
+        for lines in particles {
+            let angle = preset.direction + sample(rng, &(-preset.spread..preset.spread));
+            let rotation = burst.transform.rotation * Quat::from_rotation_z(angle.to_radians());
+            // Shards keep their orientation, only streaks turn to face where they fly.
+            let transform = match preset.shape {
+                ParticleShape::Streak { .. } => burst.transform.with_rotation(rotation),
+                ParticleShape::Shards(_) => burst.transform,
+            };
+            let velocity = rotation.mul_vec3(Vec3::Y).xy() * sample(rng, &preset.speed)
+                + burst.velocity * preset.inherit_velocity;
+            let mut particle = commands.spawn((
+                LineMesh(lines),
+                transform,
+                RigidBody::Dynamic,
+                Mass(1.),
+                LinearVelocity(velocity),
+                LinearDamping(preset.damping),
+                AngularVelocity(sample(rng, &preset.angular_speed)),
+                LevelScoped,
+            ));
+            if let Some(width) = preset.width {
+                particle.insert(width);
+            }
+            if let Some(lifetime) = &preset.lifetime {
+                particle.insert(DelayedDespawn::new(
+                    time.elapsed_secs(),
+                    sample(rng, lifetime),
+                ));
+            }
+        }
+    }
+}
+
+/// `random_range` that accepts empty ranges, returning their start.
+fn sample<T>(rng: &mut impl Rng, range: &Range<T>) -> T
+where
+    T: PartialOrd + Copy + SampleUniform,
+{
+    if range.is_empty() {
+        range.start
+    } else {
+        random_range(rng, range.clone())
+    }
+}
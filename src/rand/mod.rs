@@ -1,22 +1,131 @@
-use bevy::math::Vec2;
+use bevy::{log::info, math::Vec2, prelude::Resource, utils::hashbrown::HashMap};
 use noisy_bevy::simplex_noise_2d;
 use rand::{
     distributions::uniform::{SampleRange, SampleUniform},
-    Rng,
+    rngs::StdRng,
+    Rng, SeedableRng,
 };
 
-pub fn random_vec2_range<T: SampleRange<f32> + Clone>(range: T) -> Vec2 {
-    Vec2::new(random_range(range.clone()), random_range(range))
+/// Separate sequences of `GameRng`, so drawing more sparks doesn't change where asteroids spawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    Spawning,
+    Visuals,
+    Loot,
 }
 
-pub fn random_range<T, R: SampleRange<T>>(range: R) -> T
+/// All gameplay randomness, replayable by starting the game with `--seed <number>`.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<RngStream, StdRng>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> GameRng {
+        GameRng {
+            seed,
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Seeded from `--seed <number>` when given, from entropy otherwise. The seed is logged so
+    /// a run can be replayed.
+    pub fn from_args() -> GameRng {
+        let seed = seed_from_args(std::env::args()).unwrap_or_else(|| rand::thread_rng().gen());
+        info!("game seed: {seed}");
+        GameRng::new(seed)
+    }
+
+    /// Restarts every stream from the seed, so each level replays the same way.
+    pub fn reset(&mut self) {
+        self.streams.clear();
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        let seed = self.seed;
+        self.streams.entry(stream).or_insert_with(|| {
+            // Spreads the streams apart, nearby seeds would otherwise give related sequences.
+            StdRng::seed_from_u64(seed ^ (stream as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        })
+    }
+}
+
+/// Reads `--seed <number>` or `--seed=<number>`.
+pub fn seed_from_args(args: impl IntoIterator<Item = String>) -> Option<u64> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--seed" {
+            return args.next()?.parse().ok();
+        }
+        if let Some(value) = arg.strip_prefix("--seed=") {
+            return value.parse().ok();
+        }
+    }
+    None
+}
+
+pub fn random_vec2_range<T: SampleRange<f32> + Clone>(rng: &mut impl Rng, range: T) -> Vec2 {
+    Vec2::new(random_range(rng, range.clone()), random_range(rng, range))
+}
+
+pub fn random_range<T, R>(rng: &mut impl Rng, range: R) -> T
 where
     T: SampleUniform,
     R: SampleRange<T>,
 {
-    rand::thread_rng().gen_range(range)
+    rng.gen_range(range)
 }
 
 pub fn random_smooth(x: f32) -> f32 {
     simplex_noise_2d(Vec2::new(x, 0.))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_renderer::line_group::LineGroup;
+
+    fn random_circle(rng: &mut GameRng) -> LineGroup {
+        LineGroup::generate_random_circle(rng.stream(RngStream::Spawning), 50., 12, 5.)
+    }
+
+    #[test]
+    fn same_seed_same_circle() {
+        let first = random_circle(&mut GameRng::new(42));
+        let second = random_circle(&mut GameRng::new(42));
+        assert_eq!(first, second);
+        assert_ne!(first, random_circle(&mut GameRng::new(43)));
+    }
+
+    #[test]
+    fn reset_replays_the_streams() {
+        let mut rng = GameRng::new(7);
+        let first = random_circle(&mut rng);
+        assert_ne!(random_circle(&mut rng), first);
+        rng.reset();
+        assert_eq!(random_circle(&mut rng), first);
+    }
+
+    #[test]
+    fn streams_are_independent() {
+        let mut rng = GameRng::new(7);
+        let expected = random_circle(&mut GameRng::new(7));
+        rng.stream(RngStream::Visuals).gen::<u64>();
+        assert_eq!(random_circle(&mut rng), expected);
+    }
+
+    #[test]
+    fn seed_from_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            super::seed_from_args(args(&["game", "--seed", "12"])),
+            Some(12)
+        );
+        assert_eq!(
+            super::seed_from_args(args(&["game", "--seed=34"])),
+            Some(34)
+        );
+        assert_eq!(super::seed_from_args(args(&["game"])), None);
+    }
+}